@@ -0,0 +1,74 @@
+use std::marker::PhantomData;
+#[cfg(feature = "allocator_api")]
+use std::{alloc::Allocator, sync::Arc};
+
+use crate::{ring_buffer::RingBuffer, storage::Alloc};
+
+/// Builder of a ring buffer with custom memory alignment and allocator.
+///
+/// Created by [`RingBuffer::builder`](struct.RingBuffer.html#method.builder).
+///
+/// ```rust
+/// # extern crate ringbuf;
+/// # use ringbuf::RingBuffer;
+/// # fn main() {
+/// let (mut prod, cons) = RingBuffer::<f32>::builder(64)
+///     .align(64)
+///     .build()
+///     .split();
+///
+/// prod.push_slice(&[0.0; 16]);
+/// let (left, _) = cons.as_slices();
+/// assert_eq!(left.as_ptr() as usize % 64, 0);
+/// # }
+/// ```
+pub struct Builder<T: Sized> {
+    capacity: usize,
+    alloc: Alloc,
+    _elems: PhantomData<fn() -> T>,
+}
+
+impl<T: Sized> RingBuffer<T> {
+    /// Creates a builder of a ring buffer of given capacity allowing to configure its memory.
+    pub fn builder(capacity: usize) -> Builder<T> {
+        Builder {
+            capacity,
+            alloc: Alloc::default(),
+            _elems: PhantomData,
+        }
+    }
+}
+
+impl<T: Sized> Builder<T> {
+    /// Sets minimal alignment of the ring buffer memory in bytes.
+    ///
+    /// The natural alignment of `T` is used if it is greater.
+    /// Memory allocated on [`resize`](struct.Producer.html#method.resize) has the same alignment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn align(mut self, align: usize) -> Self {
+        assert!(
+            align.is_power_of_two(),
+            "alignment {} is not a power of two",
+            align
+        );
+        self.alloc.align = align;
+        self
+    }
+
+    /// Sets allocator of the ring buffer memory, the global allocator is used by default.
+    ///
+    /// Memory allocated on [`resize`](struct.Producer.html#method.resize) is allocated by it too.
+    #[cfg(feature = "allocator_api")]
+    pub fn allocator<A: Allocator + Send + Sync + 'static>(mut self, allocator: A) -> Self {
+        self.alloc.allocator = Some(Arc::new(allocator));
+        self
+    }
+
+    /// Creates the ring buffer.
+    pub fn build(self) -> RingBuffer<T> {
+        RingBuffer::with_alloc(self.capacity, self.alloc)
+    }
+}
@@ -1,66 +1,187 @@
 use std::{
-    cmp::min,
+    fmt,
     io::{self, Read, Write},
+    iter::{self, Chain},
     mem::{self, MaybeUninit},
     ops::Range,
     ptr::copy_nonoverlapping,
-    sync::{atomic::Ordering, Arc},
+    slice,
 };
 
-use crate::{producer::Producer, ring_buffer::*};
+#[cfg(feature = "stats")]
+use crate::stats::Stats;
+use crate::{
+    ring_buffer::*,
+    sync::{Arc, Ordering},
+    traits::*,
+};
 
 /// Consumer part of ring buffer.
+///
+/// # Thread safety
+///
+/// The consumer can be sent to another thread if the elements can be sent.
+/// It gives shared access to the stored elements, so it can be shared between threads
+/// only if the elements can be shared too.
+///
+/// ```compile_fail
+/// # extern crate ringbuf;
+/// # use ringbuf::RingBuffer;
+/// # use std::{rc::Rc, thread};
+/// # fn main() {
+/// let (_prod, cons) = RingBuffer::<Rc<i32>>::new(1).split();
+/// thread::spawn(move || drop(cons));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # extern crate ringbuf;
+/// # use ringbuf::{Consumer, RingBuffer};
+/// # use std::cell::Cell;
+/// fn assert_sync<T: Sync>(_: &T) {}
+/// # fn main() {
+/// let (_prod, cons) = RingBuffer::<Cell<i32>>::new(1).split();
+/// assert_sync(&cons);
+/// # }
+/// ```
 pub struct Consumer<T> {
     pub(crate) rb: Arc<RingBuffer<T>>,
 }
 
+unsafe impl<T: Send> Send for Consumer<T> {}
+unsafe impl<T: Send + Sync> Sync for Consumer<T> {}
+
 impl<T: Sized> Consumer<T> {
     /// Returns capacity of the ring buffer.
     ///
-    /// The capacity of the buffer is constant.
+    /// The capacity of the buffer is constant unless it is changed by [`Producer::resize`](struct.Producer.html#method.resize).
     pub fn capacity(&self) -> usize {
-        self.rb.capacity()
+        self.last_rb().capacity()
     }
 
     /// Checks if the ring buffer is empty.
     ///
     /// *The result may become irrelevant at any time because of concurring activity of the producer.*
     pub fn is_empty(&self) -> bool {
-        self.rb.is_empty()
+        self.head_rb().is_empty()
     }
 
     /// Checks if the ring buffer is full.
     ///
     /// The result is relevant until you remove items from the consumer.
     pub fn is_full(&self) -> bool {
-        self.rb.is_full()
+        self.last_rb().is_full()
     }
 
     /// The length of the data stored in the buffer
     ///
     /// Actual length may be equal to or greater than the returned value.
     pub fn len(&self) -> usize {
-        self.rb.len()
+        self.rb.chain().map(|rb| rb.len()).sum()
     }
 
     /// The remaining space in the buffer.
     ///
     /// Actual remaining space may be equal to or less than the returning value.
     pub fn remaining(&self) -> usize {
-        self.rb.remaining()
+        self.last_rb().remaining()
     }
 
-    fn get_ranges(&self) -> (Range<usize>, Range<usize>) {
-        let head = self.rb.head.load(Ordering::Acquire);
-        let tail = self.rb.tail.load(Ordering::Acquire);
-        let len = unsafe { self.rb.data.get_ref().len() };
+    /// The storage containing the oldest elements.
+    ///
+    /// It differs from the current storage if the producer has resized the buffer and the current one is drained.
+    fn head_rb(&self) -> &RingBuffer<T> {
+        let mut rb = &*self.rb;
+        while let Some(next) = rb.next() {
+            if !rb.is_empty() {
+                break;
+            }
+            rb = next;
+        }
+        rb
+    }
 
-        if head < tail {
-            (head..tail, 0..0)
-        } else if head > tail {
-            (head..len, 0..tail)
-        } else {
-            (0..0, 0..0)
+    /// The storage the producer pushes elements to.
+    fn last_rb(&self) -> &RingBuffer<T> {
+        self.rb.chain().last().unwrap()
+    }
+
+    /// Switches to the next storage if the producer has resized the buffer and the current one is drained.
+    fn follow(&mut self) {
+        while let Some(next) = self.rb.next_arc() {
+            if !self.rb.is_empty() {
+                break;
+            }
+            self.rb = next;
+        }
+    }
+
+    /// Checks if the current storage is drained and the producer has resized the buffer,
+    /// so a bulk operation should continue in the next storage.
+    fn can_follow(&self) -> bool {
+        self.rb.next().is_some() && self.rb.is_empty()
+    }
+
+    /// Slices of the elements contained by all the storages of the ring buffer, in order, starting from the oldest ones.
+    ///
+    /// # Safety
+    ///
+    /// The ring buffer must be locked or pinned while the slices are used,
+    /// so that the producer doesn't overwrite the elements.
+    pub(crate) unsafe fn chain_slices(&self) -> impl Iterator<Item = &[T]> {
+        let mut next = Some(&*self.rb);
+        iter::from_fn(move || {
+            let rb = next?;
+            // The successor is loaded first, so that the storage can't get more elements after its slices are taken.
+            next = rb.next();
+            Some(rb.occupied_slices())
+        })
+        .flat_map(|(left, right)| iter::once(left).chain(iter::once(right)))
+    }
+
+    /// Returns a snapshot of the ring buffer statistics.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.rb.stats()
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the ring buffer.
+    ///
+    /// *The slices may not include elements pushed to the buffer by concurring producer after the method call.*
+    ///
+    /// *If the producer has resized the buffer then only the elements of the oldest storage are given,
+    /// the rest of them are given after the oldest storage is drained.*
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        if let Some(lock) = self.lock() {
+            lock.pin();
+        }
+        unsafe { self.head_rb().occupied_slices() }
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the contents of the ring buffer.
+    ///
+    /// *The slices may not include elements pushed to the buffer by concurring producer after the method call.*
+    ///
+    /// *Covers only the oldest storage after resize, see [`as_slices`](#method.as_slices).*
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        if let Some(lock) = self.lock_mut() {
+            lock.pin();
+        }
+        self.occupied_mut_slices()
+    }
+
+    fn occupied_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.follow();
+        let ranges = self.rb.occupied_ranges();
+
+        unsafe {
+            let left = &mut self.rb.data.get_mut()[ranges.0];
+            let right = &mut self.rb.data.get_mut()[ranges.1];
+
+            (
+                &mut *(left as *mut [MaybeUninit<T>] as *mut [T]),
+                &mut *(right as *mut [MaybeUninit<T>] as *mut [T]),
+            )
         }
     }
 
@@ -71,18 +192,13 @@ impl<T: Sized> Consumer<T> {
     /// First slice contains older elements.
     ///
     /// *The slices may not include elements pushed to the buffer by concurring producer after the method call.*
+    ///
+    /// *If the producer has resized the buffer then only the elements of the oldest storage are given,
+    /// the rest of them are given after the oldest storage is drained.*
     pub fn access<F: FnOnce(&[T], &[T])>(&self, f: F) {
-        let ranges = self.get_ranges();
-
-        unsafe {
-            let left = &self.rb.data.get_ref()[ranges.0];
-            let right = &self.rb.data.get_ref()[ranges.1];
-
-            f(
-                &*(left as *const [MaybeUninit<T>] as *const [T]),
-                &*(right as *const [MaybeUninit<T>] as *const [T]),
-            );
-        }
+        let _lock = self.lock();
+        let (left, right) = unsafe { self.head_rb().occupied_slices() };
+        f(left, right);
     }
 
     /// Gives mutable access to the elements contained by the ring buffer without removing them.
@@ -92,24 +208,16 @@ impl<T: Sized> Consumer<T> {
     /// First slice contains older elements.
     ///
     /// *The iteration may not include elements pushed to the buffer by concurring producer after the method call.*
+    ///
+    /// *Covers only the oldest storage after resize, see [`access`](#method.access).*
     pub fn access_mut<F: FnOnce(&mut [T], &mut [T])>(&mut self, f: F) {
-        let ranges = self.get_ranges();
-
-        unsafe {
-            let left = &mut self.rb.data.get_mut()[ranges.0];
-            let right = &mut self.rb.data.get_mut()[ranges.1];
-
-            f(
-                &mut *(left as *mut [MaybeUninit<T>] as *mut [T]),
-                &mut *(right as *mut [MaybeUninit<T>] as *mut [T]),
-            );
-        }
+        let _lock = self.lock_mut();
+        let (left, right) = self.occupied_mut_slices();
+        f(left, right);
     }
 
     /// Allows to read from ring buffer memory directry.
     ///
-    /// *This function is unsafe because it gives access to possibly uninitialized memory*
-    ///
     /// The method takes a function `f` as argument.
     /// `f` takes two slices of ring buffer content (the second one or both of them may be empty).
     /// First slice contains older elements.
@@ -120,13 +228,33 @@ impl<T: Sized> Consumer<T> {
     /// The method **always** calls `f` even if ring buffer is empty.
     ///
     /// The method returns number returned from `f`.
+    ///
+    /// # Safety
+    ///
+    /// *This function is unsafe because it gives access to possibly uninitialized memory*
     pub unsafe fn pop_access<F>(&mut self, f: F) -> usize
     where
         F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize,
     {
+        let _lock = self.lock_mut();
+        let (head, tail, ranges) = self.pop_ranges();
+        let slices = (
+            &mut self.rb.data.get_mut()[ranges.0],
+            &mut self.rb.data.get_mut()[ranges.1],
+        );
+
+        let n = f(slices.0, slices.1);
+
+        self.advance_head(head, tail, n);
+        n
+    }
+
+    /// Returns head and tail positions and ranges of the occupied space of the storage.
+    fn pop_ranges(&mut self) -> (usize, usize, (Range<usize>, Range<usize>)) {
+        self.follow();
         let head = self.rb.head.load(Ordering::Acquire);
         let tail = self.rb.tail.load(Ordering::Acquire);
-        let len = self.rb.data.get_ref().len();
+        let len = unsafe { self.rb.data.get_ref() }.len();
 
         let ranges = if head < tail {
             (head..tail, 0..0)
@@ -136,18 +264,44 @@ impl<T: Sized> Consumer<T> {
             (0..0, 0..0)
         };
 
-        let slices = (
-            &mut self.rb.data.get_mut()[ranges.0],
-            &mut self.rb.data.get_mut()[ranges.1],
-        );
+        self.rb.data.touch(&ranges);
+        (head, tail, ranges)
+    }
 
-        let n = f(slices.0, slices.1);
+    /// Releases space of `n` elements removed after `head` to the producer.
+    ///
+    /// # Safety
+    ///
+    /// `head` and `tail` must be obtained from `pop_ranges`
+    /// and `n` elements at the beginning of the occupied space must be moved out.
+    #[cfg_attr(not(feature = "eventfd"), allow(unused_variables))]
+    unsafe fn advance_head(&self, head: usize, tail: usize, n: usize) {
+        let len = self.rb.data.get_ref().len();
 
         if n > 0 {
             let new_head = (head + n) % len;
             self.rb.head.store(new_head, Ordering::Release);
+            #[cfg(feature = "seq")]
+            self.rb.seq.on_pop(n);
+            if let Some(watermarks) = &self.rb.watermarks {
+                watermarks.on_pop(n);
+            }
+            self.on_space();
+            #[cfg(feature = "stats")]
+            self.rb.stats.on_pop(n);
+        }
+        #[cfg(feature = "stats")]
+        {
+            if head == tail {
+                self.rb.stats.on_pop_failure();
+            }
+        }
+        #[cfg(feature = "eventfd")]
+        {
+            if let Some(notifier) = &self.rb.notifier {
+                notifier.on_pop(n > 0, (head + n) % len == tail, || self.is_empty());
+            }
         }
-        n
     }
 
     /// Copies data from the ring buffer to the slice in byte-to-byte manner.
@@ -157,51 +311,47 @@ impl<T: Sized> Consumer<T> {
     /// The remaining part is still **un-iniitilized**.
     ///
     /// Returns the number of items been copied.
+    ///
+    /// # Safety
+    ///
+    /// *This function is unsafe because the initialization state of `elems` is changed by the call.*
     pub unsafe fn pop_copy(&mut self, elems: &mut [MaybeUninit<T>]) -> usize {
-        self.pop_access(|left, right| {
-            if elems.len() < left.len() {
-                copy_nonoverlapping(left.as_ptr(), elems.as_mut_ptr(), elems.len());
-                elems.len()
-            } else {
-                copy_nonoverlapping(left.as_ptr(), elems.as_mut_ptr(), left.len());
-                if elems.len() < left.len() + right.len() {
-                    copy_nonoverlapping(
-                        right.as_ptr(),
-                        elems.as_mut_ptr().add(left.len()),
-                        elems.len() - left.len(),
-                    );
-                    elems.len()
+        let mut count = 0;
+        loop {
+            let rest = &mut elems[count..];
+            count += self.pop_access(|left, right| {
+                if rest.len() < left.len() {
+                    copy_nonoverlapping(left.as_ptr(), rest.as_mut_ptr(), rest.len());
+                    rest.len()
                 } else {
-                    copy_nonoverlapping(
-                        right.as_ptr(),
-                        elems.as_mut_ptr().add(left.len()),
-                        right.len(),
-                    );
-                    left.len() + right.len()
+                    copy_nonoverlapping(left.as_ptr(), rest.as_mut_ptr(), left.len());
+                    if rest.len() < left.len() + right.len() {
+                        copy_nonoverlapping(
+                            right.as_ptr(),
+                            rest.as_mut_ptr().add(left.len()),
+                            rest.len() - left.len(),
+                        );
+                        rest.len()
+                    } else {
+                        copy_nonoverlapping(
+                            right.as_ptr(),
+                            rest.as_mut_ptr().add(left.len()),
+                            right.len(),
+                        );
+                        left.len() + right.len()
+                    }
                 }
+            });
+            if count == elems.len() || !self.can_follow() {
+                break count;
             }
-        })
+        }
     }
 
     /// Removes latest element from the ring buffer and returns it.
     /// Returns `None` if the ring buffer is empty.
     pub fn pop(&mut self) -> Option<T> {
-        let mut elem_mu = MaybeUninit::uninit();
-        let n = unsafe {
-            self.pop_access(|slice, _| {
-                if !slice.is_empty() {
-                    mem::swap(slice.get_unchecked_mut(0), &mut elem_mu);
-                    1
-                } else {
-                    0
-                }
-            })
-        };
-        match n {
-            0 => None,
-            1 => Some(unsafe { elem_mu.assume_init() }),
-            _ => unreachable!(),
-        }
+        RbConsumer::pop(self)
     }
 
     /// Repeatedly calls the closure `f` passing elements removed from the ring buffer to it.
@@ -209,53 +359,83 @@ impl<T: Sized> Consumer<T> {
     /// The closure is called until it returns `false` or the ring buffer is empty.
     ///
     /// The method returns number of elements been removed from the buffer.
+    ///
+    /// If the closure panics the elements passed to it before are removed from the ring buffer.
     pub fn pop_each<F: FnMut(T) -> bool>(&mut self, mut f: F, count: Option<usize>) -> usize {
-        unsafe {
-            self.pop_access(|left, right| {
-                let lb = match count {
-                    Some(n) => min(n, left.len()),
-                    None => left.len(),
-                };
-                for (i, dst) in left[0..lb].iter_mut().enumerate() {
-                    if !f(mem::replace(dst, MaybeUninit::uninit()).assume_init()) {
-                        return i + 1;
-                    }
-                }
-                if lb < left.len() {
-                    return lb;
-                }
+        let _lock = self.lock_mut();
+        let limit = count.unwrap_or(usize::MAX);
+        let mut total = 0;
+        loop {
+            let (n, stopped) = self.pop_each_storage(&mut f, limit - total);
+            total += n;
+            if stopped || total == limit || !self.can_follow() {
+                break total;
+            }
+        }
+    }
 
-                let rb = match count {
-                    Some(n) => min(n - lb, right.len()),
-                    None => right.len(),
-                };
-                for (i, dst) in right[0..rb].iter_mut().enumerate() {
-                    if !f(mem::replace(dst, MaybeUninit::uninit()).assume_init()) {
-                        return i + lb + 1;
-                    }
-                }
-                left.len() + right.len()
-            })
+    /// Removes at most `count` elements of the current storage passing them to `f`.
+    ///
+    /// Returns the number of elements been removed and whether `f` has returned `false`.
+    fn pop_each_storage<F: FnMut(T) -> bool>(&mut self, f: &mut F, count: usize) -> (usize, bool) {
+        let (head, tail, ranges) = self.pop_ranges();
+        let mut guard = PopGuard {
+            cons: self,
+            head,
+            tail,
+            count: 0,
+        };
+        let slices = unsafe {
+            (
+                &mut guard.cons.rb.data.get_mut()[ranges.0],
+                &mut guard.cons.rb.data.get_mut()[ranges.1],
+            )
+        };
+        let elems = slices.0.iter_mut().chain(slices.1.iter_mut());
+        for src in elems.take(count) {
+            guard.count += 1;
+            if !f(unsafe { mem::replace(src, MaybeUninit::uninit()).assume_init() }) {
+                return (guard.count, true);
+            }
         }
+        (guard.count, false)
+    }
+
+    /// Returns an iterator over the elements contained by the ring buffer without removing them.
+    ///
+    /// *The iteration may not include elements pushed to the buffer by concurring producer after the method call.*
+    ///
+    /// *Covers only the oldest storage after resize, see [`as_slices`](#method.as_slices).*
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        RbConsumer::iter(self)
+    }
+
+    /// Returns a mutable iterator over the elements contained by the ring buffer without removing them.
+    ///
+    /// *The iteration may not include elements pushed to the buffer by concurring producer after the method call.*
+    ///
+    /// *Covers only the oldest storage after resize, see [`as_slices`](#method.as_slices).*
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        RbConsumer::iter_mut(self)
     }
 
     /// Iterate immutably over the elements contained by the ring buffer without removing them.
     ///
     /// *The iteration may not include elements pushed to the buffer by concurring producer after the method call.*
     pub fn for_each<F: FnMut(&T)>(&self, mut f: F) {
-        self.access(|left, right| {
-            for c in left.iter() {
+        let _lock = self.lock();
+        for segment in unsafe { self.chain_slices() } {
+            for c in segment.iter() {
                 f(c);
             }
-            for c in right.iter() {
-                f(c);
-            }
-        });
+        }
     }
 
     /// Iterate mutably over the elements contained by the ring buffer without removing them.
     ///
     /// *The iteration may not include elements pushed to the buffer by concurring producer after the method call.*
+    ///
+    /// *Covers only the oldest storage after resize, see [`access`](#method.access).*
     pub fn for_each_mut<F: FnMut(&mut T)>(&mut self, mut f: F) {
         self.access_mut(|left, right| {
             for c in left.iter_mut() {
@@ -271,6 +451,17 @@ impl<T: Sized> Consumer<T> {
     ///
     /// Returns the number of deleted items.
     pub fn discard(&mut self, n: usize) -> usize {
+        let mut total = 0;
+        loop {
+            total += self.discard_storage(n - total);
+            if total == n || !self.can_follow() {
+                break total;
+            }
+        }
+    }
+
+    /// Removes at most `n` items of the current storage and drops them.
+    fn discard_storage(&mut self, n: usize) -> usize {
         unsafe { self.pop_access(|left, right| {
             let (mut cnt, mut rem) = (0, n);
             let left_elems = if rem <= left.len() {
@@ -298,16 +489,165 @@ impl<T: Sized> Consumer<T> {
         }) }
     }
 
+    /// Removes elements while `pred` returns `true` for them and passes them to `f`.
+    ///
+    /// The first element not matching `pred` is left in the ring buffer.
+    ///
+    /// Returns the number of elements been removed.
+    pub fn pop_while<P, F>(&mut self, mut pred: P, mut f: F) -> usize
+    where
+        P: FnMut(&T) -> bool,
+        F: FnMut(T),
+    {
+        let _lock = self.lock_mut();
+        let mut total = 0;
+        loop {
+            let (n, stopped) = self.pop_while_storage(&mut pred, &mut f);
+            total += n;
+            if stopped || !self.can_follow() {
+                break total;
+            }
+        }
+    }
+
+    /// Removes elements of the current storage while `pred` returns `true` for them and passes them to `f`.
+    ///
+    /// Returns the number of elements been removed and whether `pred` has returned `false`.
+    fn pop_while_storage<P, F>(&mut self, pred: &mut P, f: &mut F) -> (usize, bool)
+    where
+        P: FnMut(&T) -> bool,
+        F: FnMut(T),
+    {
+        let (head, tail, ranges) = self.pop_ranges();
+        let mut guard = PopGuard {
+            cons: self,
+            head,
+            tail,
+            count: 0,
+        };
+        let slices = unsafe {
+            (
+                &mut guard.cons.rb.data.get_mut()[ranges.0],
+                &mut guard.cons.rb.data.get_mut()[ranges.1],
+            )
+        };
+        for src in slices.0.iter_mut().chain(slices.1.iter_mut()) {
+            if !pred(unsafe { &*src.as_ptr() }) {
+                return (guard.count, true);
+            }
+            guard.count += 1;
+            f(unsafe { mem::replace(src, MaybeUninit::uninit()).assume_init() });
+        }
+        (guard.count, false)
+    }
+
+    /// Removes and drops elements while `pred` returns `true` for them.
+    ///
+    /// Returns the number of elements been removed.
+    pub fn discard_while<P: FnMut(&T) -> bool>(&mut self, pred: P) -> usize {
+        self.pop_while(pred, drop)
+    }
+
+    /// Removes and drops elements until `pred` returns `true` for one of them, e.g. to find a sync word.
+    ///
+    /// The matching element is left in the ring buffer.
+    ///
+    /// Returns the number of elements been removed.
+    pub fn discard_until<P: FnMut(&T) -> bool>(&mut self, mut pred: P) -> usize {
+        self.discard_while(|elem| !pred(elem))
+    }
+
+    /// Returns the index of the first stored element for which `pred` returns `true`
+    /// without removing any elements.
+    pub fn position<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+        let _lock = self.lock();
+        unsafe { self.chain_slices() }
+            .flat_map(|segment| segment.iter())
+            .position(pred)
+    }
+
     /// Removes at most `count` elements from the consumer and appends them to the producer.
     /// If `count` is `None` then as much as possible elements will be moved.
     /// The producer and consumer parts may be of different buffers as well as of the same one.
     ///
     /// On success returns count of elements been moved.
-    pub fn move_to(&mut self, other: &mut Producer<T>, count: Option<usize>) -> usize {
+    pub fn move_to<P: RbProducer<T>>(&mut self, other: &mut P, count: Option<usize>) -> usize {
         move_items(self, other, count)
     }
 }
 
+/// Commits elements removed so far when dropped, even on unwind.
+struct PopGuard<'a, T> {
+    cons: &'a Consumer<T>,
+    head: usize,
+    tail: usize,
+    count: usize,
+}
+
+impl<'a, T> Drop for PopGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe { self.cons.advance_head(self.head, self.tail, self.count) };
+    }
+}
+
+impl<T> Drop for Consumer<T> {
+    fn drop(&mut self) {
+        self.on_close();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Consumer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Unlike `as_slices` the scoped lock doesn't leave the elements pinned after formatting.
+        let _lock = self.lock();
+        let segments = unsafe { self.chain_slices() }.collect::<Vec<_>>();
+        f.debug_struct("Consumer")
+            .field("capacity", &self.capacity())
+            .field("len", &segments.iter().map(|segment| segment.len()).sum::<usize>())
+            .field("elements", &DebugElements(&segments))
+            .finish()
+    }
+}
+
+impl<T: Sized> RbBase<T> for Consumer<T> {
+    fn capacity(&self) -> usize {
+        Consumer::capacity(self)
+    }
+
+    fn len(&self) -> usize {
+        Consumer::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Consumer::is_empty(self)
+    }
+
+    fn is_full(&self) -> bool {
+        Consumer::is_full(self)
+    }
+
+    fn remaining(&self) -> usize {
+        Consumer::remaining(self)
+    }
+}
+
+impl<T: Sized> RbConsumer<T> for Consumer<T> {
+    unsafe fn pop_access<F>(&mut self, f: F) -> usize
+    where
+        F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize,
+    {
+        Consumer::pop_access(self, f)
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        Consumer::as_slices(self)
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        Consumer::as_mut_slices(self)
+    }
+}
+
 impl<T: Sized + Copy> Consumer<T> {
     /// Removes first elements from the ring buffer and writes them into a slice.
     /// Elements should be [`Copy`](https://doc.rust-lang.org/std/marker/trait.Copy.html).
@@ -0,0 +1,123 @@
+use std::{io, mem};
+
+use crate::{consumer::Consumer, producer::Producer};
+
+/// Maximal length of a 64-bit varint.
+const VARINT_MAX_LEN: usize = 10;
+
+macro_rules! endian {
+    ($($t:ty: $push_le:ident, $push_be:ident, $pop_le:ident, $pop_be:ident, $peek_le:ident, $peek_be:ident;)*) => {$(
+        impl Producer<u8> {
+            #[doc = concat!("Appends `", stringify!($t), "` in little-endian byte order if there is space for all its bytes.")]
+            ///
+            /// Returns `true` if the value has been appended or dropped according to the [`Policy`](enum.Policy.html).
+            pub fn $push_le(&mut self, value: $t) -> bool {
+                self.push_whole(&value.to_le_bytes())
+            }
+
+            #[doc = concat!("Appends `", stringify!($t), "` in big-endian byte order if there is space for all its bytes.")]
+            ///
+            /// Returns `true` if the value has been appended or dropped according to the [`Policy`](enum.Policy.html).
+            pub fn $push_be(&mut self, value: $t) -> bool {
+                self.push_whole(&value.to_be_bytes())
+            }
+        }
+
+        impl Consumer<u8> {
+            #[doc = concat!("Removes `", stringify!($t), "` stored in little-endian byte order.")]
+            ///
+            /// Returns `None` if not all its bytes are stored, in that case nothing is removed.
+            pub fn $pop_le(&mut self) -> Option<$t> {
+                self.pop_pod::<[u8; mem::size_of::<$t>()]>()
+                    .map(<$t>::from_le_bytes)
+            }
+
+            #[doc = concat!("Removes `", stringify!($t), "` stored in big-endian byte order.")]
+            ///
+            /// Returns `None` if not all its bytes are stored, in that case nothing is removed.
+            pub fn $pop_be(&mut self) -> Option<$t> {
+                self.pop_pod::<[u8; mem::size_of::<$t>()]>()
+                    .map(<$t>::from_be_bytes)
+            }
+
+            #[doc = concat!("Reads `", stringify!($t), "` stored in little-endian byte order without removing it.")]
+            pub fn $peek_le(&self) -> Option<$t> {
+                self.peek_pod::<[u8; mem::size_of::<$t>()]>()
+                    .map(<$t>::from_le_bytes)
+            }
+
+            #[doc = concat!("Reads `", stringify!($t), "` stored in big-endian byte order without removing it.")]
+            pub fn $peek_be(&self) -> Option<$t> {
+                self.peek_pod::<[u8; mem::size_of::<$t>()]>()
+                    .map(<$t>::from_be_bytes)
+            }
+        }
+    )*};
+}
+
+endian! {
+    u16: push_u16_le, push_u16_be, pop_u16_le, pop_u16_be, peek_u16_le, peek_u16_be;
+    u32: push_u32_le, push_u32_be, pop_u32_le, pop_u32_be, peek_u32_le, peek_u32_be;
+    u64: push_u64_le, push_u64_be, pop_u64_le, pop_u64_be, peek_u64_le, peek_u64_be;
+    i16: push_i16_le, push_i16_be, pop_i16_le, pop_i16_be, peek_i16_le, peek_i16_be;
+    i32: push_i32_le, push_i32_be, pop_i32_le, pop_i32_be, peek_i32_le, peek_i32_be;
+    i64: push_i64_le, push_i64_be, pop_i64_le, pop_i64_be, peek_i64_le, peek_i64_be;
+    f32: push_f32_le, push_f32_be, pop_f32_le, pop_f32_be, peek_f32_le, peek_f32_be;
+    f64: push_f64_le, push_f64_be, pop_f64_le, pop_f64_be, peek_f64_le, peek_f64_be;
+}
+
+impl Producer<u8> {
+    /// Appends `value` encoded as unsigned LEB128 varint if there is space for all its bytes.
+    ///
+    /// Returns `true` if the value has been appended or dropped according to the [`Policy`](enum.Policy.html).
+    pub fn push_varint(&mut self, mut value: u64) -> bool {
+        let mut bytes = [0; VARINT_MAX_LEN];
+        let mut len = 0;
+        loop {
+            bytes[len] = (value & 0x7F) as u8;
+            value >>= 7;
+            len += 1;
+            if value == 0 {
+                break;
+            }
+            bytes[len - 1] |= 0x80;
+        }
+        self.push_whole(&bytes[..len])
+    }
+}
+
+impl Consumer<u8> {
+    /// Removes a value encoded as unsigned LEB128 varint.
+    ///
+    /// Returns `Ok(None)` if the varint isn't stored completely, in that case nothing is removed.
+    ///
+    /// Returns an error of `InvalidData` kind if the varint doesn't fit into 64 bits
+    /// (it is longer than 10 bytes or its 10th byte has more than one bit set),
+    /// in that case nothing is removed too.
+    pub fn pop_varint(&mut self) -> io::Result<Option<u64>> {
+        // Keeps the producer from overwriting the varint between the calls below.
+        let _lock = self.lock_mut();
+        let mut decoded = Ok(None);
+        let mut value = 0u64;
+        // The bytes of the varint may be split between storages if the buffer has been resized.
+        let bytes = unsafe { self.chain_slices() }.flat_map(|segment| segment.iter());
+        for (i, &byte) in bytes.take(VARINT_MAX_LEN).enumerate() {
+            if i + 1 == VARINT_MAX_LEN && byte > 1 {
+                decoded = Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Varint exceeds 64 bits",
+                ));
+                break;
+            }
+            value |= u64::from(byte & 0x7F) << (7 * i);
+            if byte & 0x80 == 0 {
+                decoded = Ok(Some((value, i + 1)));
+                break;
+            }
+        }
+        Ok(decoded?.map(|(value, len)| {
+            self.discard(len);
+            value
+        }))
+    }
+}
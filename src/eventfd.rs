@@ -0,0 +1,216 @@
+use std::{
+    io,
+    ops::{Deref, DerefMut},
+    os::unix::io::{AsRawFd, RawFd},
+};
+
+use crate::{
+    consumer::Consumer,
+    producer::Producer,
+    ring_buffer::RingBuffer,
+    sync::{fence, Arc, AtomicBool, Mutex, Ordering},
+};
+
+/// Non-blocking `eventfd` being readable while it is signaled.
+struct EventFd {
+    fd: RawFd,
+    /// Whether the descriptor is readable, modified only under `lock`.
+    signaled: AtomicBool,
+    lock: Mutex<()>,
+}
+
+impl EventFd {
+    fn new() -> io::Result<Self> {
+        let fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            fd,
+            signaled: AtomicBool::new(false),
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Makes the descriptor readable.
+    ///
+    /// The system call is made only if the descriptor isn't readable yet.
+    fn signal(&self) {
+        fence(Ordering::SeqCst);
+        if !self.signaled.load(Ordering::Relaxed) {
+            let _guard = self.lock.lock().unwrap();
+            if !self.signaled.load(Ordering::Relaxed) {
+                // Set the flag first so that concurrent `reset` waits for the write to complete.
+                self.signaled.store(true, Ordering::Relaxed);
+                let value = 1u64;
+                unsafe { libc::write(self.fd, &value as *const u64 as *const libc::c_void, 8) };
+            }
+        }
+    }
+
+    /// Makes the descriptor non-readable.
+    ///
+    /// The caller must check the condition after that and signal again if it still holds.
+    fn reset(&self) {
+        if self.signaled.load(Ordering::Relaxed) {
+            let _guard = self.lock.lock().unwrap();
+            if self.signaled.load(Ordering::Relaxed) {
+                let mut value = 0u64;
+                unsafe { libc::read(self.fd, &mut value as *mut u64 as *mut libc::c_void, 8) };
+                self.signaled.store(false, Ordering::Relaxed);
+            }
+        }
+        fence(Ordering::SeqCst);
+    }
+}
+
+impl Drop for EventFd {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+/// Pair of `eventfd`s reflecting the ring buffer state.
+pub(crate) struct Notifier {
+    /// Readable while the ring buffer isn't empty.
+    data: EventFd,
+    /// Readable while the ring buffer isn't full.
+    space: EventFd,
+}
+
+impl Notifier {
+    fn new() -> io::Result<Self> {
+        Ok(Self {
+            data: EventFd::new()?,
+            space: EventFd::new()?,
+        })
+    }
+
+    /// Called by the producer, `full` means that the buffer could become full.
+    pub fn on_push<F: FnOnce() -> bool>(&self, pushed: bool, full: bool, is_full: F) {
+        if pushed {
+            self.data.signal();
+        }
+        if full {
+            self.space.reset();
+            if !is_full() {
+                self.space.signal();
+            }
+        }
+    }
+
+    /// Called by the consumer, `empty` means that the buffer could become empty.
+    pub fn on_pop<F: FnOnce() -> bool>(&self, popped: bool, empty: bool, is_empty: F) {
+        if popped {
+            self.space.signal();
+        }
+        if empty {
+            self.data.reset();
+            if !is_empty() {
+                self.data.signal();
+            }
+        }
+    }
+}
+
+/// Producer with `eventfd` being readable while the ring buffer isn't full.
+///
+/// Dereferences to [`Producer`](struct.Producer.html).
+/// The descriptor is reset when the producer finds the ring buffer full
+/// and signaled when the consumer removes elements from it after that,
+/// so there are no system calls on each operation.
+///
+/// Available only on Linux with `eventfd` feature enabled.
+pub struct EventProducer<T> {
+    prod: Producer<T>,
+}
+
+/// Consumer with `eventfd` being readable while the ring buffer isn't empty.
+///
+/// Dereferences to [`Consumer`](struct.Consumer.html).
+/// The descriptor is reset when the consumer finds the ring buffer empty
+/// and signaled when the producer appends elements to it after that,
+/// so there are no system calls on each operation.
+///
+/// Available only on Linux with `eventfd` feature enabled.
+pub struct EventConsumer<T> {
+    cons: Consumer<T>,
+}
+
+impl<T: Sized> RingBuffer<T> {
+    /// Splits ring buffer into producer and consumer with `eventfd`s attached to them.
+    ///
+    /// The descriptors can be registered in `epoll` (or `mio` via `SourceFd`) to wait until
+    /// the consumer has some data or the producer has some space.
+    pub fn split_eventfd(mut self) -> io::Result<(EventProducer<T>, EventConsumer<T>)> {
+        let notifier = Notifier::new()?;
+        if !self.is_empty() {
+            notifier.data.signal();
+        }
+        if !self.is_full() {
+            notifier.space.signal();
+        }
+        self.notifier = Some(Arc::new(notifier));
+
+        let (prod, cons) = self.split();
+        Ok((EventProducer { prod }, EventConsumer { cons }))
+    }
+}
+
+impl<T> EventProducer<T> {
+    /// Returns the underlying producer.
+    ///
+    /// The descriptor remains signaled properly while the consumer part exists.
+    pub fn into_inner(self) -> Producer<T> {
+        self.prod
+    }
+}
+
+impl<T> EventConsumer<T> {
+    /// Returns the underlying consumer.
+    ///
+    /// The descriptor remains signaled properly while the producer part exists.
+    pub fn into_inner(self) -> Consumer<T> {
+        self.cons
+    }
+}
+
+impl<T> Deref for EventProducer<T> {
+    type Target = Producer<T>;
+
+    fn deref(&self) -> &Producer<T> {
+        &self.prod
+    }
+}
+
+impl<T> DerefMut for EventProducer<T> {
+    fn deref_mut(&mut self) -> &mut Producer<T> {
+        &mut self.prod
+    }
+}
+
+impl<T> Deref for EventConsumer<T> {
+    type Target = Consumer<T>;
+
+    fn deref(&self) -> &Consumer<T> {
+        &self.cons
+    }
+}
+
+impl<T> DerefMut for EventConsumer<T> {
+    fn deref_mut(&mut self) -> &mut Consumer<T> {
+        &mut self.cons
+    }
+}
+
+impl<T> AsRawFd for EventProducer<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.prod.rb.notifier.as_ref().unwrap().space.fd
+    }
+}
+
+impl<T> AsRawFd for EventConsumer<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.cons.rb.notifier.as_ref().unwrap().data.fd
+    }
+}
@@ -0,0 +1,273 @@
+use std::{cmp::min, mem::MaybeUninit};
+
+use crate::{consumer::Consumer, producer::Producer, ring_buffer::RingBuffer};
+
+/// Producer appending only whole frames of `channels` samples.
+///
+/// Created by [`RingBuffer::split_frames`](struct.RingBuffer.html#method.split_frames).
+pub struct FrameProducer<T> {
+    prod: Producer<T>,
+    channels: usize,
+}
+
+/// Consumer removing only whole frames of `channels` samples.
+///
+/// Created by [`RingBuffer::split_frames`](struct.RingBuffer.html#method.split_frames).
+pub struct FrameConsumer<T> {
+    cons: Consumer<T>,
+    channels: usize,
+}
+
+impl<T: Sized> RingBuffer<T> {
+    /// Creates a ring buffer holding `frames` frames of `channels` samples each
+    /// and splits it into frame-aware producer and consumer.
+    ///
+    /// The storage size is a multiple of frame size and the parts move only whole frames,
+    /// so a frame is never split between the two slices of the ring buffer contents.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channels` is zero.
+    pub fn split_frames(frames: usize, channels: usize) -> (FrameProducer<T>, FrameConsumer<T>) {
+        assert!(channels > 0, "frame must contain at least one channel");
+        let (prod, cons) = Self::new((frames + 1) * channels - 1).split();
+        (
+            FrameProducer { prod, channels },
+            FrameConsumer { cons, channels },
+        )
+    }
+}
+
+/// Number of frames in the storage including the one that is always vacant.
+fn storage_frames<T>(rb: &RingBuffer<T>, channels: usize) -> usize {
+    (rb.capacity() + 1) / channels
+}
+
+impl<T: Sized> FrameProducer<T> {
+    /// Number of samples in a frame.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Returns capacity of the ring buffer in frames.
+    pub fn capacity(&self) -> usize {
+        storage_frames(&self.prod.rb, self.channels) - 1
+    }
+
+    /// The number of frames stored in the buffer.
+    ///
+    /// Actual number may be equal to or less than the returned value.
+    pub fn len(&self) -> usize {
+        self.prod.len() / self.channels
+    }
+
+    /// Checks if the ring buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.prod.is_empty()
+    }
+
+    /// Checks if there is no space for a frame in the ring buffer.
+    pub fn is_full(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// The number of frames that can be appended to the buffer.
+    ///
+    /// Actual number may be equal to or greater than the returned value.
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Returns the underlying producer.
+    pub fn into_inner(self) -> Producer<T> {
+        self.prod
+    }
+
+    /// Calls `f` for vacant space of at most `frames` frames and commits the returned number of frames.
+    ///
+    /// Each slice passed to `f` consists of whole frames.
+    fn push_frames<F>(&mut self, frames: usize, f: F) -> usize
+    where
+        F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize,
+    {
+        let channels = self.channels;
+        let samples = unsafe {
+            self.prod.push_access(|left, right| {
+                let left_frames = min(left.len() / channels, frames);
+                let right_frames = min(right.len() / channels, frames - left_frames);
+                let (left, right) = (
+                    &mut left[..(left_frames * channels)],
+                    &mut right[..(right_frames * channels)],
+                );
+                f(left, right) * channels
+            })
+        };
+        samples / channels
+    }
+}
+
+impl<T: Sized + Copy> FrameProducer<T> {
+    /// Appends frames from interleaved samples.
+    /// Trailing samples that don't form a whole frame are ignored.
+    ///
+    /// Returns the number of frames been appended.
+    pub fn push_interleaved(&mut self, samples: &[T]) -> usize {
+        let channels = self.channels;
+        self.push_frames(samples.len() / channels, |left, right| {
+            for (dst, src) in left.iter_mut().chain(right.iter_mut()).zip(samples) {
+                *dst = MaybeUninit::new(*src);
+            }
+            (left.len() + right.len()) / channels
+        })
+    }
+
+    /// Appends frames from planar samples, one slice per channel.
+    /// The number of frames is limited by the shortest slice.
+    ///
+    /// Returns the number of frames been appended.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of slices isn't equal to the number of channels.
+    pub fn push_planar(&mut self, planes: &[&[T]]) -> usize {
+        assert_eq!(planes.len(), self.channels, "wrong number of channels");
+        let channels = self.channels;
+        let frames = planes.iter().map(|p| p.len()).min().unwrap();
+        self.push_frames(frames, |left, right| {
+            let left_frames = left.len() / channels;
+            for (i, frame) in left
+                .chunks_exact_mut(channels)
+                .chain(right.chunks_exact_mut(channels))
+                .enumerate()
+            {
+                for (dst, plane) in frame.iter_mut().zip(planes) {
+                    *dst = MaybeUninit::new(plane[i]);
+                }
+            }
+            left_frames + right.len() / channels
+        })
+    }
+}
+
+impl<T: Sized> FrameConsumer<T> {
+    /// Number of samples in a frame.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Returns capacity of the ring buffer in frames.
+    pub fn capacity(&self) -> usize {
+        storage_frames(&self.cons.rb, self.channels) - 1
+    }
+
+    /// The number of frames stored in the buffer.
+    ///
+    /// Actual number may be equal to or greater than the returned value.
+    pub fn len(&self) -> usize {
+        self.cons.len() / self.channels
+    }
+
+    /// Checks if the ring buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+
+    /// Checks if there is no space for a frame in the ring buffer.
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns the underlying consumer.
+    pub fn into_inner(self) -> Consumer<T> {
+        self.cons
+    }
+
+    /// Calls `f` for at most `frames` stored frames and removes the returned number of frames.
+    ///
+    /// Each slice passed to `f` consists of whole frames.
+    fn pop_frames<F>(&mut self, frames: usize, f: F) -> usize
+    where
+        F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize,
+    {
+        let channels = self.channels;
+        let samples = unsafe {
+            self.cons.pop_access(|left, right| {
+                let left_frames = min(left.len() / channels, frames);
+                let right_frames = min(right.len() / channels, frames - left_frames);
+                let (left, right) = (
+                    &mut left[..(left_frames * channels)],
+                    &mut right[..(right_frames * channels)],
+                );
+                f(left, right) * channels
+            })
+        };
+        samples / channels
+    }
+}
+
+impl<T: Sized + Copy> FrameConsumer<T> {
+    /// Gives access to stored frames as interleaved samples and removes frames afterwards.
+    ///
+    /// `f` takes two slices of whole frames (the second one or both of them may be empty),
+    /// the first slice contains older frames. `f` returns the number of frames to remove.
+    ///
+    /// Returns the number of frames been removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns more frames than it has been given.
+    pub fn access_frames<F: FnOnce(&[T], &[T]) -> usize>(&mut self, f: F) -> usize {
+        let channels = self.channels;
+        self.pop_frames(usize::MAX, |left, right| {
+            let frames = (left.len() + right.len()) / channels;
+            let n = unsafe {
+                f(
+                    &*(left as *const [MaybeUninit<T>] as *const [T]),
+                    &*(right as *const [MaybeUninit<T>] as *const [T]),
+                )
+            };
+            assert!(n <= frames, "too many frames to remove");
+            n
+        })
+    }
+
+    /// Removes frames from the ring buffer and writes them as interleaved samples.
+    /// Trailing samples that don't form a whole frame are left untouched.
+    ///
+    /// Returns the number of frames been removed.
+    pub fn pop_interleaved(&mut self, samples: &mut [T]) -> usize {
+        let channels = self.channels;
+        self.pop_frames(samples.len() / channels, |left, right| {
+            for (dst, src) in samples.iter_mut().zip(left.iter().chain(right.iter())) {
+                *dst = unsafe { src.assume_init() };
+            }
+            (left.len() + right.len()) / channels
+        })
+    }
+
+    /// Removes frames from the ring buffer and writes them as planar samples, one slice per channel.
+    /// The number of frames is limited by the shortest slice.
+    ///
+    /// Returns the number of frames been removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of slices isn't equal to the number of channels.
+    pub fn pop_planar(&mut self, planes: &mut [&mut [T]]) -> usize {
+        assert_eq!(planes.len(), self.channels, "wrong number of channels");
+        let channels = self.channels;
+        let frames = planes.iter().map(|p| p.len()).min().unwrap();
+        self.pop_frames(frames, |left, right| {
+            for (i, frame) in left
+                .chunks_exact(channels)
+                .chain(right.chunks_exact(channels))
+                .enumerate()
+            {
+                for (src, plane) in frame.iter().zip(planes.iter_mut()) {
+                    plane[i] = unsafe { src.assume_init() };
+                }
+            }
+            (left.len() + right.len()) / channels
+        })
+    }
+}
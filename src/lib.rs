@@ -10,12 +10,55 @@
 //!
 //! Elements can be effectively appended/removed one by one or many at once.
 //! Also data could be loaded/stored directly into/from [`Read`]/[`Write`] instances.
+//! Delimiters can be searched for in stored bytes via `Consumer::find_byte` and `Consumer::find_subslice`.
+//! Fixed-layout values implementing `Pod` can be transferred through a byte ring buffer via `Producer::push_pod` and `Consumer::pop_pod`,
+//! integers can be encoded in either byte order or as varints via methods like `Producer::push_u32_le` and `Consumer::pop_varint`.
 //! And finally, there are `unsafe` methods allowing thread-safe direct access in place to the inner memory being appended/removed.
 //!
+//! By default the producer rejects elements that don't fit into the full ring buffer,
+//! other behaviour can be chosen via `RingBuffer::with_policy`.
+//! Note that with `Policy::OverwriteOldest` the producer and the consumer aren't lock-free.
+//!
+//! Capacity of a split ring buffer can be changed by the producer via `Producer::resize` without stopping the consumer.
+//!
+//! Elements can be read ahead without removing them via `Consumer::begin` and removed only when the transaction is committed.
+//!
+//! Flow control can be driven by high and low watermarks of the ring buffer length
+//! set via `RingBuffer::with_watermarks` or `RingBuffer::with_watermark_callback`.
+//!
+//! Multi-channel data such as audio can be transferred in whole frames via `RingBuffer::split_frames`
+//! converting between interleaved and planar layouts.
+//!
+//! Elements can be timestamped on push via `RingBuffer::split_timed` to track their age and evict stale ones.
+//!
+//! When only the most recent value matters, `TripleBuffer` passes it between threads without queueing.
+//!
+//! Common functionality of `Producer` and `Consumer` is also exposed through `RbProducer` and `RbConsumer` traits
+//! allowing to write generic code working with wrappers or alternative implementations of ring buffer parts.
+//!
 //! [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
 //! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
 //!
 //! When building with nightly toolchain it is possible to run benchmarks via `cargo bench --features benchmark`.
+//! Also the `allocator_api` feature allows to pass a custom allocator to `RingBuffer::builder`
+//! which otherwise configures memory alignment of a ring buffer of given capacity.
+//!
+//! Memory ordering of the ring buffer is checked by [`loom`](https://docs.rs/loom) model tests
+//! run via `RUSTFLAGS="--cfg loom" cargo test --release --lib tests::loom`.
+//!
+//! Enabling `stats` feature makes ring buffer count pushed and popped elements, failed operations and peak occupancy.
+//! The counters are available via `stats()` method of `RingBuffer`, `Producer` and `Consumer`.
+//!
+//! Enabling `seq` feature gives every element a sequence number on push,
+//! see `Producer::push_with_seq` and `Consumer::pop_with_seq`. It requires 64-bit atomics.
+//!
+//! Enabling `select` feature allows a thread serving several consumers to wait until any of them has data via `Select`.
+//!
+//! Enabling `serde` feature allows to serialize and deserialize `RingBuffer` as its capacity and sequence of stored elements.
+//! Contents of split ring buffer can be serialized via `Consumer::snapshot`.
+//!
+//! Enabling `eventfd` feature (Linux only) allows to split ring buffer via `RingBuffer::split_eventfd`
+//! into halves providing file descriptors to wait for data or space in `epoll` loop.
 //!
 //! # Examples
 //!
@@ -115,20 +158,67 @@
 //!
 
 #![cfg_attr(feature = "benchmark", feature(test))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 #[cfg(feature = "benchmark")]
 extern crate test;
 
+#[cfg(all(feature = "eventfd", not(target_os = "linux")))]
+compile_error!("`eventfd` feature is supported only on Linux");
+#[cfg(all(feature = "seq", not(target_has_atomic = "64")))]
+compile_error!("`seq` feature requires 64-bit atomics");
+
 #[cfg(feature = "benchmark")]
 mod benchmark;
 
 #[cfg(test)]
 mod tests;
 
+mod builder;
 mod consumer;
+mod endian;
+#[cfg(feature = "eventfd")]
+mod eventfd;
+mod frames;
+mod pod;
+mod policy;
 mod producer;
 mod ring_buffer;
+mod search;
+#[cfg(feature = "select")]
+mod select;
+#[cfg(feature = "seq")]
+mod sequence;
+mod storage;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "stats")]
+mod stats;
+mod sync;
+mod timed;
+mod traits;
+mod transaction;
+mod triple;
+mod waker;
+mod watermark;
 
+pub use builder::Builder;
 pub use consumer::*;
+#[cfg(feature = "eventfd")]
+pub use eventfd::{EventConsumer, EventProducer};
+pub use frames::{FrameConsumer, FrameProducer};
+pub use pod::Pod;
+pub use policy::Policy;
 pub use producer::*;
 pub use ring_buffer::*;
+#[cfg(feature = "select")]
+pub use select::Select;
+#[cfg(feature = "serde")]
+pub use serialize::Snapshot;
+#[cfg(feature = "stats")]
+pub use stats::Stats;
+pub use timed::{Clock, MonotonicClock, TimedConsumer, TimedProducer};
+pub use traits::*;
+pub use transaction::Transaction;
+pub use triple::{TripleBuffer, TripleConsumer, TripleProducer};
+pub use watermark::Watermark;
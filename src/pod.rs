@@ -0,0 +1,125 @@
+use std::{
+    cmp::min,
+    mem::{self, MaybeUninit},
+    ptr, slice,
+};
+
+use crate::{consumer::Consumer, producer::Producer};
+
+/// Plain old data type that can be transferred through a byte ring buffer as is.
+///
+/// # Safety
+///
+/// The type must have no padding bytes and any bit pattern of its size must be a valid value of it.
+/// Structures implementing it should be `#[repr(C)]` or `#[repr(transparent)]`.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Reads a value from the beginning of concatenation of `segments`.
+///
+/// The value is read in place if it is contained in the first segment, otherwise it is staged through a stack copy.
+fn read_pod<'a, T: Pod, I: Iterator<Item = &'a [u8]>>(segments: I) -> Option<T> {
+    let size = mem::size_of::<T>();
+    let mut value = MaybeUninit::<T>::uninit();
+    let dst = value.as_mut_ptr() as *mut u8;
+    let mut count = 0;
+    for segment in segments {
+        if count == 0 && segment.len() >= size {
+            return Some(unsafe { ptr::read_unaligned(segment.as_ptr() as *const T) });
+        }
+        let n = min(segment.len(), size - count);
+        unsafe { ptr::copy_nonoverlapping(segment.as_ptr(), dst.add(count), n) };
+        count += n;
+        if count == size {
+            return Some(unsafe { value.assume_init() });
+        }
+    }
+    None
+}
+
+/// Bytes of the memory occupied by `value`.
+fn pod_bytes<T: Pod>(value: &T) -> &[u8] {
+    unsafe { slice::from_raw_parts(value as *const T as *const u8, mem::size_of::<T>()) }
+}
+
+impl Producer<u8> {
+    /// Appends bytes of `value` to the ring buffer if there is space for all of them.
+    ///
+    /// If the ring buffer is full then it is handled according to the [`Policy`](enum.Policy.html).
+    ///
+    /// Returns `true` if the value has been appended or dropped,
+    /// `false` is always returned if the value is larger than the capacity of the ring buffer.
+    pub fn push_pod<T: Pod>(&mut self, value: &T) -> bool {
+        self.push_whole(pod_bytes(value))
+    }
+
+    /// Appends `bytes` to the ring buffer if there is space for all of them.
+    ///
+    /// Returns `true` if the bytes have been appended or dropped.
+    /// Bytes that can't fit into the ring buffer even if it is empty are neither appended nor dropped.
+    pub(crate) fn push_whole(&mut self, bytes: &[u8]) -> bool {
+        if bytes.len() > self.capacity() {
+            return false;
+        }
+        let deadline = self.deadline();
+        loop {
+            let n = unsafe {
+                self.push_access(|left, right| {
+                    if left.len() + right.len() < bytes.len() {
+                        return 0;
+                    }
+                    let (head, tail) = bytes.split_at(left.len().min(bytes.len()));
+                    ptr::copy_nonoverlapping(
+                        head.as_ptr(),
+                        left.as_mut_ptr() as *mut u8,
+                        head.len(),
+                    );
+                    ptr::copy_nonoverlapping(
+                        tail.as_ptr(),
+                        right.as_mut_ptr() as *mut u8,
+                        tail.len(),
+                    );
+                    bytes.len()
+                })
+            };
+            if n > 0 || bytes.is_empty() {
+                return true;
+            }
+            if !self.make_room_all(bytes.len(), deadline) {
+                break;
+            }
+        }
+        self.drop_newest(bytes.len()) > 0
+    }
+}
+
+impl Consumer<u8> {
+    /// Reads a value from the oldest stored bytes without removing them.
+    ///
+    /// Returns `None` if there are not enough bytes stored.
+    pub fn peek_pod<T: Pod>(&self) -> Option<T> {
+        let _lock = self.lock();
+        // The bytes of the value may be split between storages if the buffer has been resized.
+        read_pod(unsafe { self.chain_slices() })
+    }
+
+    /// Removes bytes of a value from the ring buffer and returns the value.
+    ///
+    /// Returns `None` if there are not enough bytes stored, in that case nothing is removed.
+    pub fn pop_pod<T: Pod>(&mut self) -> Option<T> {
+        // Keeps the producer from overwriting the bytes between the read and the removal.
+        let _lock = self.lock_mut();
+        let value = read_pod(unsafe { self.chain_slices() })?;
+        self.discard(mem::size_of::<T>());
+        Some(value)
+    }
+}
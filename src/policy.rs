@@ -0,0 +1,348 @@
+use std::{
+    cmp::min,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    consumer::Consumer,
+    producer::Producer,
+    ring_buffer::RingBuffer,
+    sync::{fence, spin_loop, Arc, AtomicBool, AtomicUsize, Ordering},
+    waker::Waker,
+};
+
+/// Behaviour of the producer when the ring buffer is full.
+///
+/// The policy is honoured by `push`, `push_each`, `push_iter`, `push_slice` and `read_from` methods of `Producer`
+/// and by its `Write` implementation. Low-level `push_access` and `push_copy` methods ignore it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Policy {
+    /// Elements that don't fit are left to the caller.
+    #[default]
+    Reject,
+    /// Elements that don't fit are dropped and counted as discarded.
+    DropNewest,
+    /// The oldest elements are dropped to make room for new ones and counted as discarded.
+    /// After resize the elements left in the previous storages are dropped first.
+    ///
+    /// Elements aren't overwritten while the consumer holds slices or iterators borrowed from it
+    /// (obtained by `as_slices`, `iter` or their mutable counterparts) until its next method taking `&mut self`,
+    /// in that case the elements are left to the caller as with `Reject` policy.
+    ///
+    /// *This policy isn't lock-free:* the producer dropping elements and the consumer accessing them
+    /// exclude each other by a spin lock, so one of them may wait for the other to finish its operation.
+    /// The lock is taken only with this policy and, by the producer, only when the ring buffer is full.
+    OverwriteOldest,
+    /// The producer waits for space, at most for the given time if any.
+    /// Elements that don't fit on timeout or after the consumer has been dropped are left to the caller.
+    Block(Option<Duration>),
+}
+
+/// The producer is dropping the oldest elements.
+const EVICTING: usize = 1;
+/// The consumer holds borrowed slices.
+const PINNED: usize = 2;
+/// One consumer operation accessing the stored elements.
+const READER: usize = 4;
+
+/// Full-buffer handling state shared by all storages of the ring buffer.
+pub(crate) struct Overflow {
+    pub(crate) policy: Policy,
+    /// Number of elements dropped by the policy.
+    discarded: AtomicUsize,
+    /// Lock excluding eviction of the oldest elements while the consumer accesses them,
+    /// used only with `OverwriteOldest` policy.
+    state: AtomicUsize,
+    /// The producer waiting for space.
+    pub(crate) space: Waker,
+    /// The consumer has been dropped, so there will be no more space.
+    closed: AtomicBool,
+}
+
+impl Overflow {
+    pub(crate) fn new(policy: Policy) -> Self {
+        Self {
+            policy,
+            discarded: AtomicUsize::new(0),
+            state: AtomicUsize::new(0),
+            space: Waker::default(),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn discard(&self, n: usize) {
+        self.discarded.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Locks the oldest elements for eviction, fails if the consumer holds borrowed slices.
+    fn lock_evict(&self) -> Option<EvictLock<'_>> {
+        loop {
+            match self.state.compare_exchange_weak(
+                0,
+                EVICTING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(EvictLock(self)),
+                Err(state) if state & PINNED != 0 => return None,
+                Err(_) => spin_loop(),
+            }
+        }
+    }
+
+    fn lock_read(this: &Arc<Self>) -> ReadLock {
+        loop {
+            if this.state.fetch_add(READER, Ordering::Acquire) & EVICTING == 0 {
+                return ReadLock(this.clone());
+            }
+            this.state.fetch_sub(READER, Ordering::Relaxed);
+            while this.state.load(Ordering::Relaxed) & EVICTING != 0 {
+                spin_loop();
+            }
+        }
+    }
+}
+
+struct EvictLock<'a>(&'a Overflow);
+
+impl<'a> Drop for EvictLock<'a> {
+    fn drop(&mut self) {
+        self.0.state.fetch_sub(EVICTING, Ordering::Release);
+    }
+}
+
+/// Keeps the oldest elements from being evicted while the consumer accesses them.
+pub(crate) struct ReadLock(Arc<Overflow>);
+
+impl ReadLock {
+    /// Keeps the elements locked after the lock is dropped until the next `Consumer::lock_mut` call.
+    pub(crate) fn pin(&self) {
+        self.0.state.fetch_or(PINNED, Ordering::Relaxed);
+    }
+}
+
+impl Drop for ReadLock {
+    fn drop(&mut self) {
+        self.0.state.fetch_sub(READER, Ordering::Release);
+    }
+}
+
+impl<T: Sized> RingBuffer<T> {
+    /// Sets the behaviour of the producer when the ring buffer is full.
+    ///
+    /// ```rust
+    /// # extern crate ringbuf;
+    /// # use ringbuf::{Policy, RingBuffer};
+    /// # fn main() {
+    /// let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+    ///     .with_policy(Policy::OverwriteOldest)
+    ///     .split();
+    ///
+    /// assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    /// assert_eq!(prod.discarded(), 1);
+    /// assert_eq!(cons.pop(), Some(1));
+    /// assert_eq!(cons.pop(), Some(2));
+    /// # }
+    /// ```
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.overflow = Arc::new(Overflow::new(policy));
+        self
+    }
+}
+
+impl<T: Sized> Producer<T> {
+    /// Returns the behaviour of the producer when the ring buffer is full.
+    pub fn policy(&self) -> Policy {
+        self.rb.overflow.policy
+    }
+
+    /// Returns the number of elements dropped by the full-buffer policy so far.
+    pub fn discarded(&self) -> usize {
+        self.rb.overflow.discarded.load(Ordering::Relaxed)
+    }
+
+    /// Time the producer may wait for space till during a single operation.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        match self.policy() {
+            Policy::Block(Some(timeout)) => Some(Instant::now() + timeout),
+            _ => None,
+        }
+    }
+
+    /// Makes room for `n` more elements in the full ring buffer if the policy allows.
+    ///
+    /// Returns `false` if no more elements can be appended.
+    pub(crate) fn make_room(&self, n: usize, deadline: Option<Instant>) -> bool {
+        let made = match self.policy() {
+            Policy::Reject | Policy::DropNewest => false,
+            // The consumer may have made room concurrently.
+            Policy::OverwriteOldest => {
+                self.evict(min(n, self.capacity())) > 0 || !self.rb.is_full()
+            }
+            Policy::Block(_) => self.wait_for_space(1, deadline),
+        };
+        self.count_failure(made)
+    }
+
+    /// Same as `make_room` but makes room for all `n` elements at once.
+    ///
+    /// `n` must not exceed the capacity.
+    pub(crate) fn make_room_all(&self, n: usize, deadline: Option<Instant>) -> bool {
+        match self.policy() {
+            Policy::Block(_) => self.count_failure(self.wait_for_space(n, deadline)),
+            _ => self.make_room(n - min(n, self.remaining()), deadline),
+        }
+    }
+
+    /// Counts a failed push if no room has been `made`, as the elements are rejected or dropped then.
+    ///
+    /// Returns `made`.
+    fn count_failure(&self, made: bool) -> bool {
+        #[cfg(feature = "stats")]
+        {
+            if !made {
+                self.rb.stats.on_push_failure();
+            }
+        }
+        made
+    }
+
+    /// Counts `n` elements that don't fit as dropped if the policy is `DropNewest`.
+    ///
+    /// Returns the number of dropped elements.
+    pub(crate) fn drop_newest(&self, n: usize) -> usize {
+        if self.policy() == Policy::DropNewest {
+            self.rb.overflow.discard(n);
+            n
+        } else {
+            0
+        }
+    }
+
+    /// Drops the elements left in the storages before resize, as they are older,
+    /// and then at most `n` oldest elements of the current storage.
+    ///
+    /// Nothing is dropped while the consumer holds borrowed slices.
+    fn evict(&self, n: usize) -> usize {
+        let overflow = &self.rb.overflow;
+        let _lock = match overflow.lock_evict() {
+            Some(lock) => lock,
+            None => return 0,
+        };
+        let count = self
+            .first_rb()
+            .chain()
+            .map(|rb| {
+                let limit = if rb.next().is_some() { usize::MAX } else { n };
+                unsafe { rb.evict(limit) }
+            })
+            .sum();
+        overflow.discard(count);
+        count
+    }
+
+    /// Waits until there is space for `n` elements in the ring buffer or `deadline` passes.
+    fn wait_for_space(&self, n: usize, deadline: Option<Instant>) -> bool {
+        let waker = &self.rb.overflow.space;
+        waker.register();
+        // Pairs with the fence in `Waker::wake` called by the consumer.
+        fence(Ordering::SeqCst);
+        let result = loop {
+            if self.rb.remaining() >= n {
+                break true;
+            }
+            if self.rb.overflow.closed.load(Ordering::Acquire) {
+                break false;
+            }
+            match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break false;
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+                None => thread::park(),
+            }
+        };
+        waker.unregister();
+        result
+    }
+}
+
+impl<T: Sized> RingBuffer<T> {
+    /// Drops at most `n` oldest elements of the storage as the consumer would remove them.
+    ///
+    /// # Safety
+    ///
+    /// Must be called by the producer holding the eviction lock.
+    #[cfg_attr(not(feature = "eventfd"), allow(unused_variables))]
+    unsafe fn evict(&self, n: usize) -> usize {
+        let (left, right) = self.occupied_ranges();
+        self.data.touch(&(left.clone(), right.clone()));
+        let data = self.data.get_mut();
+        let mut count = 0;
+        for i in left.chain(right).take(n) {
+            data[i].as_mut_ptr().drop_in_place();
+            count += 1;
+        }
+        if count > 0 {
+            let len = data.len();
+            let head = self.head.load(Ordering::Acquire);
+            let tail = self.tail.load(Ordering::Acquire);
+            self.head.store((head + count) % len, Ordering::Release);
+            #[cfg(feature = "seq")]
+            self.seq.on_pop(count);
+            if let Some(watermarks) = &self.watermarks {
+                watermarks.on_pop(count);
+            }
+            #[cfg(feature = "stats")]
+            self.stats.on_pop(count);
+            #[cfg(feature = "eventfd")]
+            {
+                if let Some(notifier) = &self.notifier {
+                    notifier.on_pop(true, (head + count) % len == tail, || self.is_empty());
+                }
+            }
+        }
+        count
+    }
+}
+
+impl<T: Sized> Consumer<T> {
+    /// Keeps the stored elements from being overwritten by the producer while the lock exists.
+    pub(crate) fn lock(&self) -> Option<ReadLock> {
+        let overflow = &self.rb.overflow;
+        if overflow.policy == Policy::OverwriteOldest {
+            Some(Overflow::lock_read(overflow))
+        } else {
+            None
+        }
+    }
+
+    /// Same as `lock` but also releases the elements pinned before,
+    /// as there can be no slices borrowed from the consumer.
+    pub(crate) fn lock_mut(&mut self) -> Option<ReadLock> {
+        let lock = self.lock()?;
+        lock.0.state.fetch_and(!PINNED, Ordering::Release);
+        Some(lock)
+    }
+
+    /// Called after the consumer has released space.
+    pub(crate) fn on_space(&self) {
+        let overflow = &self.rb.overflow;
+        if let Policy::Block(_) = overflow.policy {
+            overflow.space.wake();
+        }
+    }
+
+    /// Called when the consumer is dropped, so that the producer stops waiting for space.
+    pub(crate) fn on_close(&self) {
+        let overflow = &self.rb.overflow;
+        overflow.closed.store(true, Ordering::Release);
+        if let Policy::Block(_) = overflow.policy {
+            overflow.space.wake();
+        }
+    }
+}
@@ -1,21 +1,53 @@
 use std::{
+    cell::RefCell,
+    cmp::{max, min},
+    fmt,
     io::{self, Read, Write},
+    iter,
     mem::{self, MaybeUninit},
+    ops::Range,
     ptr::copy_nonoverlapping,
-    sync::{atomic::Ordering, Arc},
 };
 
-use crate::{consumer::Consumer, ring_buffer::*};
+#[cfg(feature = "stats")]
+use crate::stats::Stats;
+use crate::{
+    policy::Policy,
+    ring_buffer::*,
+    sync::{Arc, Ordering},
+    traits::*,
+};
 
 /// Producer part of ring buffer.
+///
+/// # Thread safety
+///
+/// The producer can be sent to another thread if the elements can be sent.
+/// The producer gives no access to the stored elements,
+/// so it can also be shared between threads under the same condition.
+///
+/// ```compile_fail
+/// # extern crate ringbuf;
+/// # use ringbuf::RingBuffer;
+/// # use std::{rc::Rc, thread};
+/// # fn main() {
+/// let (prod, _cons) = RingBuffer::<Rc<i32>>::new(1).split();
+/// thread::spawn(move || drop(prod));
+/// # }
+/// ```
 pub struct Producer<T> {
     pub(crate) rb: Arc<RingBuffer<T>>,
+    /// The oldest of the storages left after resize that the consumer may not have drained yet.
+    pub(crate) pending: Option<Arc<RingBuffer<T>>>,
 }
 
+unsafe impl<T: Send> Send for Producer<T> {}
+unsafe impl<T: Send> Sync for Producer<T> {}
+
 impl<T: Sized> Producer<T> {
     /// Returns capacity of the ring buffer.
     ///
-    /// The capacity of the buffer is constant.
+    /// The capacity of the buffer is constant unless it is changed by [`resize`](#method.resize).
     pub fn capacity(&self) -> usize {
         self.rb.capacity()
     }
@@ -24,7 +56,7 @@ impl<T: Sized> Producer<T> {
     ///
     /// The result is relevant until you push items to the producer.
     pub fn is_empty(&self) -> bool {
-        self.rb.is_empty()
+        self.first_rb().chain().all(|rb| rb.is_empty())
     }
 
     /// Checks if the ring buffer is full.
@@ -38,7 +70,7 @@ impl<T: Sized> Producer<T> {
     ///
     /// Actual length may be equal to or less than the returned value.
     pub fn len(&self) -> usize {
-        self.rb.len()
+        self.first_rb().chain().map(|rb| rb.len()).sum()
     }
 
     /// The remaining space in the buffer.
@@ -48,9 +80,53 @@ impl<T: Sized> Producer<T> {
         self.rb.remaining()
     }
 
-    /// Allows to write into ring buffer memory directry.
+    /// Returns a snapshot of the ring buffer statistics.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.rb.stats()
+    }
+
+    /// Changes capacity of the ring buffer without interrupting the consumer.
     ///
-    /// *This function is unsafe because it gives access to possibly uninitialized memory*
+    /// A new storage of given `capacity` is allocated and linked after the current one,
+    /// all subsequently pushed elements go to the new storage.
+    /// The consumer removes the elements remaining in the previous storage first
+    /// and then switches to the new one, so the order of elements is preserved.
+    /// The previous storage is freed once it is drained.
+    ///
+    /// `capacity` may be less than the current length of the buffer,
+    /// in that case elements that don't fit aren't lost but the producer has to wait until they are consumed.
+    pub fn resize(&mut self, capacity: usize) {
+        self.release_drained();
+        let next = Arc::new(self.rb.successor(capacity));
+        self.rb.link(next.clone());
+        let prev = mem::replace(&mut self.rb, next);
+        if self.pending.is_none() {
+            self.pending = Some(prev);
+        }
+    }
+
+    /// The oldest storage that may contain elements.
+    pub(crate) fn first_rb(&self) -> &RingBuffer<T> {
+        self.pending.as_ref().unwrap_or(&self.rb)
+    }
+
+    /// Releases the storages left after resize that the consumer has drained.
+    fn release_drained(&mut self) {
+        while let Some(rb) = self.pending.take() {
+            // The storage has a successor, so it remains empty once drained.
+            if !rb.is_empty() {
+                self.pending = Some(rb);
+                break;
+            }
+            let next = rb.next_arc().unwrap();
+            if !Arc::ptr_eq(&next, &self.rb) {
+                self.pending = Some(next);
+            }
+        }
+    }
+
+    /// Allows to write into ring buffer memory directry.
     ///
     /// The method takes a function `f` as argument.
     /// `f` takes two slices of ring buffer content (the second one or both of them may be empty).
@@ -62,13 +138,32 @@ impl<T: Sized> Producer<T> {
     /// The method **always** calls `f` even if ring buffer is full.
     ///
     /// The method returns number returned from `f`.
+    ///
+    /// # Safety
+    ///
+    /// *This function is unsafe because it gives access to possibly uninitialized memory*
     pub unsafe fn push_access<F>(&mut self, f: F) -> usize
     where
         F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize,
     {
+        self.release_drained();
+        let (head, tail, ranges) = self.push_ranges();
+        let slices = (
+            &mut self.rb.data.get_mut()[ranges.0],
+            &mut self.rb.data.get_mut()[ranges.1],
+        );
+
+        let n = f(slices.0, slices.1);
+
+        self.advance_tail(head, tail, n);
+        n
+    }
+
+    /// Returns head and tail positions and ranges of the vacant space of the storage.
+    fn push_ranges(&self) -> (usize, usize, (Range<usize>, Range<usize>)) {
         let head = self.rb.head.load(Ordering::Acquire);
         let tail = self.rb.tail.load(Ordering::Acquire);
-        let len = self.rb.data.get_ref().len();
+        let len = unsafe { self.rb.data.get_ref() }.len();
 
         let ranges = if tail >= head {
             if head > 0 {
@@ -84,18 +179,41 @@ impl<T: Sized> Producer<T> {
             (0..0, 0..0)
         };
 
-        let slices = (
-            &mut self.rb.data.get_mut()[ranges.0],
-            &mut self.rb.data.get_mut()[ranges.1],
-        );
+        self.rb.data.touch(&ranges);
+        (head, tail, ranges)
+    }
 
-        let n = f(slices.0, slices.1);
+    /// Makes `n` elements written after `tail` available to the consumer.
+    ///
+    /// # Safety
+    ///
+    /// `head` and `tail` must be obtained from `push_ranges`
+    /// and `n` elements at the beginning of the vacant space must be initialized.
+    #[cfg_attr(not(feature = "eventfd"), allow(unused_variables))]
+    unsafe fn advance_tail(&self, head: usize, tail: usize, n: usize) {
+        let len = self.rb.data.get_ref().len();
 
         if n > 0 {
             let new_tail = (tail + n) % len;
+            // The elements are counted before the consumer can remove them, so the count never underflows.
+            if let Some(watermarks) = &self.rb.watermarks {
+                watermarks.on_push(n);
+            }
             self.rb.tail.store(new_tail, Ordering::Release);
+            #[cfg(feature = "seq")]
+            self.rb.seq.on_push(n);
+            #[cfg(feature = "select")]
+            self.rb.waker.wake();
+            // The length includes elements left in previous storages after resize.
+            #[cfg(feature = "stats")]
+            self.rb.stats.on_push(n, self.len(), tail + n >= len);
+        }
+        #[cfg(feature = "eventfd")]
+        {
+            if let Some(notifier) = &self.rb.notifier {
+                notifier.on_push(n > 0, (tail + n + 1) % len == head, || self.rb.is_full());
+            }
         }
-        n
     }
 
     /// Copies data from the slice to the ring buffer in byte-to-byte manner.
@@ -104,6 +222,10 @@ impl<T: Sized> Producer<T> {
     /// After the call the copied part of data in `elems` should be interpreted as **un-initialized**.
     ///
     /// Returns the number of items been copied.
+    ///
+    /// # Safety
+    ///
+    /// *This function is unsafe because the initialization state of `elems` is changed by the call.*
     pub unsafe fn push_copy(&mut self, elems: &[MaybeUninit<T>]) -> usize {
         self.push_access(|left, right| -> usize {
             if elems.len() < left.len() {
@@ -132,56 +254,106 @@ impl<T: Sized> Producer<T> {
 
     /// Appends an element to the ring buffer.
     /// On failure returns an error containing the element that hasn't beed appended.
+    ///
+    /// If the ring buffer is full then the element is handled according to the [`Policy`](enum.Policy.html).
     pub fn push(&mut self, elem: T) -> Result<(), T> {
-        let mut elem_mu = MaybeUninit::new(elem);
-        let n = unsafe {
-            self.push_access(|slice, _| {
-                if !slice.is_empty() {
-                    mem::swap(slice.get_unchecked_mut(0), &mut elem_mu);
-                    1
-                } else {
-                    0
-                }
-            })
-        };
-        match n {
-            0 => Err(unsafe { elem_mu.assume_init() }),
-            1 => Ok(()),
-            _ => unreachable!(),
+        match RbProducer::push(self, elem) {
+            // The element is rejected according to the policy there, so the failure is counted once.
+            Err(elem) => {
+                let mut elems = iter::once(elem);
+                self.push_iter(&mut elems);
+                elems.next().map_or(Ok(()), Err)
+            }
+            result => result,
         }
     }
 
     /// Repeatedly calls the closure `f` and pushes elements returned from it to the ring buffer.
     ///
     /// The closure is called until it returns `None` or the ring buffer is full.
+    /// If the ring buffer is full then it is handled according to the [`Policy`](enum.Policy.html):
+    /// with `DropNewest` policy the element returned by the closure that doesn't fit is dropped,
+    /// with `OverwriteOldest` or `Block` ones the closure is called again when there is space for its element.
     ///
-    /// The method returns number of elements been put into the buffer.
-    pub fn push_each<F: FnMut() -> Option<T>>(&mut self, mut f: F) -> usize {
-        unsafe {
-            self.push_access(|left, right| {
-                for (i, dst) in left.iter_mut().enumerate() {
-                    match f() {
-                        Some(e) => mem::replace(dst, MaybeUninit::new(e)),
-                        None => return i,
-                    };
-                }
-                for (i, dst) in right.iter_mut().enumerate() {
-                    match f() {
-                        Some(e) => mem::replace(dst, MaybeUninit::new(e)),
-                        None => return i + left.len(),
-                    };
+    /// The method returns number of elements been put into the buffer or dropped.
+    /// If the closure panics the elements returned before are kept in the ring buffer.
+    pub fn push_each<F: FnMut() -> Option<T>>(&mut self, f: F) -> usize {
+        self.push_each_hinted(f, || (1, None))
+    }
+
+    /// Same as `push_each` but `hint` returns bounds of the number of elements remaining in `f`
+    /// as `Iterator::size_hint` does.
+    fn push_each_hinted<F, H>(&mut self, mut f: F, hint: H) -> usize
+    where
+        F: FnMut() -> Option<T>,
+        H: Fn() -> (usize, Option<usize>),
+    {
+        let deadline = self.deadline();
+        let mut count = 0;
+        loop {
+            let (n, exhausted) = self.push_vacant(&mut f);
+            count += n;
+            if exhausted || hint().1 == Some(0) {
+                break;
+            }
+            if !self.make_room(max(hint().0, 1), deadline) {
+                if self.policy() == Policy::DropNewest {
+                    // The closure may never return `None`, so only as many elements as it is known to have are dropped.
+                    for _ in 0..hint().1.unwrap_or(1) {
+                        match f() {
+                            Some(elem) => {
+                                drop(elem);
+                                count += self.drop_newest(1);
+                            }
+                            None => break,
+                        }
+                    }
                 }
-                left.len() + right.len()
-            })
+                break;
+            }
         }
+        count
+    }
+
+    /// Pushes elements returned from `f` into the vacant space.
+    ///
+    /// Returns the number of elements been pushed and whether `f` has returned `None`.
+    fn push_vacant<F: FnMut() -> Option<T>>(&mut self, f: &mut F) -> (usize, bool) {
+        self.release_drained();
+        let (head, tail, ranges) = self.push_ranges();
+        let mut guard = PushGuard {
+            prod: self,
+            head,
+            tail,
+            count: 0,
+        };
+        let slices = unsafe {
+            (
+                &mut guard.prod.rb.data.get_mut()[ranges.0],
+                &mut guard.prod.rb.data.get_mut()[ranges.1],
+            )
+        };
+        for dst in slices.0.iter_mut().chain(slices.1.iter_mut()) {
+            match f() {
+                Some(e) => *dst = MaybeUninit::new(e),
+                None => return (guard.count, true),
+            }
+            guard.count += 1;
+        }
+        (guard.count, false)
     }
 
     /// Appends elements from an iterator to the ring buffer.
     /// Elements that haven't been added to the ring buffer remain in the iterator.
     ///
-    /// Returns count of elements been appended to the ring buffer.
+    /// If the ring buffer is full then it is handled according to the [`Policy`](enum.Policy.html),
+    /// with `DropNewest` policy the iterator is drained if it has an upper bound of its length,
+    /// otherwise only the first element that doesn't fit is dropped.
+    ///
+    /// Returns count of elements been appended to the ring buffer or dropped.
     pub fn push_iter<I: Iterator<Item = T>>(&mut self, elems: &mut I) -> usize {
-        self.push_each(|| elems.next())
+        let elems = RefCell::new(elems);
+        self.push_each_hinted(|| elems.borrow_mut().next(), || elems.borrow().size_hint())
     }
 
     /// Removes at most `count` elements from the consumer and appends them to the producer.
@@ -189,21 +361,89 @@ impl<T: Sized> Producer<T> {
     /// The producer and consumer parts may be of different buffers as well as of the same one.
     ///
     /// On success returns number of elements been moved.
-    pub fn move_from(&mut self, other: &mut Consumer<T>, count: Option<usize>) -> usize {
+    pub fn move_from<C: RbConsumer<T>>(&mut self, other: &mut C, count: Option<usize>) -> usize {
         move_items(other, self, count)
     }
 }
 
+/// Commits elements pushed so far when dropped, even on unwind.
+struct PushGuard<'a, T> {
+    prod: &'a Producer<T>,
+    head: usize,
+    tail: usize,
+    count: usize,
+}
+
+impl<'a, T> Drop for PushGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe { self.prod.advance_tail(self.head, self.tail, self.count) };
+    }
+}
+
+impl<T: Sized> fmt::Debug for Producer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T: Sized> RbBase<T> for Producer<T> {
+    fn capacity(&self) -> usize {
+        self.rb.capacity()
+    }
+
+    fn len(&self) -> usize {
+        Producer::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Producer::is_empty(self)
+    }
+
+    fn is_full(&self) -> bool {
+        self.rb.is_full()
+    }
+
+    fn remaining(&self) -> usize {
+        Producer::remaining(self)
+    }
+}
+
+impl<T: Sized> RbProducer<T> for Producer<T> {
+    unsafe fn push_access<F>(&mut self, f: F) -> usize
+    where
+        F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize,
+    {
+        Producer::push_access(self, f)
+    }
+}
+
 impl<T: Sized + Copy> Producer<T> {
     /// Appends elements from slice to the ring buffer.
     /// Elements should be [`Copy`](https://doc.rust-lang.org/std/marker/trait.Copy.html).
     ///
-    /// Returns count of elements been appended to the ring buffer.
+    /// If the ring buffer is full then it is handled according to the [`Policy`](enum.Policy.html).
+    ///
+    /// Returns count of elements been appended to the ring buffer or dropped.
     pub fn push_slice(&mut self, elems: &[T]) -> usize {
-        unsafe { self.push_copy(&*(elems as *const [T] as *const [MaybeUninit<T>])) }
+        let deadline = self.deadline();
+        let mut count = 0;
+        loop {
+            let rest = &elems[count..];
+            count += unsafe { self.push_copy(&*(rest as *const [T] as *const [MaybeUninit<T>])) };
+            if count == elems.len() || !self.make_room(elems.len() - count, deadline) {
+                break;
+            }
+        }
+        count + self.drop_newest(elems.len() - count)
     }
 }
 
+/// Maximal number of bytes read at once into the full ring buffer if the number isn't limited by the caller.
+const FULL_READ_CHUNK: usize = 256;
+
 impl Producer<u8> {
     /// Reads at most `count` bytes
     /// from [`Read`](https://doc.rust-lang.org/std/io/trait.Read.html) instance
@@ -214,7 +454,24 @@ impl Producer<u8> {
     /// `n == 0` means that either `read` returned zero or ring buffer is full.
     ///
     /// If `read` is failed then error is returned.
+    ///
+    /// If the ring buffer is full then it is handled according to the [`Policy`](enum.Policy.html):
+    /// with `DropNewest` policy bytes are read and dropped,
+    /// with `OverwriteOldest` one space for `count` bytes (or at most 256 bytes if `count` is `None`) is freed.
     pub fn read_from(&mut self, reader: &mut dyn Read, count: Option<usize>) -> io::Result<usize> {
+        let deadline = self.deadline();
+        let chunk = min(count.unwrap_or(FULL_READ_CHUNK), FULL_READ_CHUNK);
+        while self.rb.is_full() && count != Some(0) {
+            if !self.make_room(count.unwrap_or(chunk), deadline) {
+                if self.policy() == Policy::DropNewest {
+                    let mut buffer = [0; FULL_READ_CHUNK];
+                    let n = reader.read(&mut buffer[..chunk])?;
+                    return Ok(self.drop_newest(n));
+                }
+                break;
+            }
+        }
+
         let mut err = None;
         let n = unsafe {
             self.push_access(|left, _| -> usize {
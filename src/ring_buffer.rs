@@ -1,60 +1,266 @@
 use std::{
     cell::UnsafeCell,
     cmp::min,
-    mem::{self, MaybeUninit},
+    collections::VecDeque,
+    fmt,
+    iter::{self, FromIterator},
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
     ptr::{self, copy},
-    sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc,
-    },
+    slice,
 };
 
-use crate::{consumer::Consumer, producer::Producer};
+#[cfg(feature = "eventfd")]
+use crate::eventfd::Notifier;
+#[cfg(feature = "stats")]
+use crate::stats::{Counters, Stats};
+#[cfg(feature = "seq")]
+use crate::sequence::Sequence;
+#[cfg(feature = "select")]
+use crate::waker::Waker;
+use crate::{
+    consumer::Consumer,
+    policy::{Overflow, Policy},
+    producer::Producer,
+    storage::{Alloc, Storage},
+    sync::{Arc, AtomicPtr, AtomicUsize, Ordering},
+    traits::*,
+    watermark::Watermarks,
+};
 
 pub(crate) struct SharedVec<T: Sized> {
-    cell: UnsafeCell<Vec<T>>,
+    cell: UnsafeCell<Storage<T>>,
+    /// Models of the element slots, checking that accesses to them are synchronized.
+    #[cfg(loom)]
+    slots: Vec<loom::cell::UnsafeCell<()>>,
 }
 
-unsafe impl<T: Sized> Sync for SharedVec<T> {}
+unsafe impl<T: Sized + Sync> Sync for SharedVec<T> {}
 
 impl<T: Sized> SharedVec<T> {
-    pub fn new(data: Vec<T>) -> Self {
+    pub fn new(data: Storage<T>) -> Self {
         Self {
+            #[cfg(loom)]
+            slots: (0..data.len())
+                .map(|_| loom::cell::UnsafeCell::new(()))
+                .collect(),
             cell: UnsafeCell::new(data),
         }
     }
-    pub unsafe fn get_ref(&self) -> &Vec<T> {
+    pub unsafe fn get_ref(&self) -> &Storage<T> {
         &*self.cell.get()
     }
     #[allow(clippy::mut_from_ref)]
-    pub unsafe fn get_mut(&self) -> &mut Vec<T> {
+    pub unsafe fn get_mut(&self) -> &mut Storage<T> {
         &mut *self.cell.get()
     }
+    /// Marks elements in `ranges` as being modified.
+    #[cfg(loom)]
+    pub fn touch(&self, ranges: &(Range<usize>, Range<usize>)) {
+        let (left, right) = ranges.clone();
+        for slot in self.slots[left].iter().chain(&self.slots[right]) {
+            slot.with_mut(|_| ());
+        }
+    }
+    #[cfg(not(loom))]
+    #[inline]
+    pub fn touch(&self, _ranges: &(Range<usize>, Range<usize>)) {}
 }
 
 /// Ring buffer itself.
 pub struct RingBuffer<T: Sized> {
-    pub(crate) data: SharedVec<MaybeUninit<T>>,
+    pub(crate) data: SharedVec<T>,
     pub(crate) head: AtomicUsize,
     pub(crate) tail: AtomicUsize,
+    /// Storage the producer has switched to after resize, owned by this one.
+    pub(crate) next: AtomicPtr<RingBuffer<T>>,
+    /// Thread selecting on the consumer.
+    #[cfg(feature = "select")]
+    pub(crate) waker: Arc<Waker>,
+    /// Length thresholds notified on crossing.
+    pub(crate) watermarks: Option<Arc<Watermarks>>,
+    /// Behaviour of the producer when the storage is full.
+    pub(crate) overflow: Arc<Overflow>,
+    /// Sequence numbers of stored elements.
+    #[cfg(feature = "seq")]
+    pub(crate) seq: Arc<Sequence>,
+    #[cfg(feature = "stats")]
+    pub(crate) stats: Arc<Counters>,
+    #[cfg(feature = "eventfd")]
+    pub(crate) notifier: Option<Arc<Notifier>>,
 }
 
 impl<T: Sized> RingBuffer<T> {
     /// Creates a new instance of a ring buffer.
     pub fn new(capacity: usize) -> Self {
-        let mut data = Vec::new();
-        data.resize_with(capacity + 1, MaybeUninit::uninit);
+        Self::with_alloc(capacity, Alloc::default())
+    }
+
+    /// Creates an empty ring buffer allocating its memory by `alloc`.
+    pub(crate) fn with_alloc(capacity: usize, alloc: Alloc) -> Self {
+        let len = capacity.checked_add(1).expect("capacity overflow");
+        Self::from_data(Storage::new(len, alloc), 0)
+    }
+
+    /// Creates a ring buffer of given capacity containing elements from the vector.
+    /// The first element of the vector will be the first one to be removed from the ring buffer.
+    ///
+    /// The memory of the vector is reused, so there is no reallocation
+    /// if the vector capacity is at least `capacity + 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector length is greater than `capacity`.
+    pub fn from_vec(vec: Vec<T>, capacity: usize) -> Self {
+        assert!(
+            vec.len() <= capacity,
+            "vector length {} exceeds ring buffer capacity {}",
+            vec.len(),
+            capacity
+        );
+        let len = vec.len();
+        let mut vec = ManuallyDrop::new(vec);
+        let mut data = unsafe {
+            Vec::from_raw_parts(vec.as_mut_ptr() as *mut MaybeUninit<T>, len, vec.capacity())
+        };
+        data.resize_with(
+            capacity.checked_add(1).expect("capacity overflow"),
+            MaybeUninit::uninit,
+        );
+        Self::from_data(Storage::from_vec(data), len)
+    }
+
+    /// Creates a ring buffer from its memory, `tail` first elements of `data` should be initialized.
+    fn from_data(data: Storage<T>, tail: usize) -> Self {
         Self {
             data: SharedVec::new(data),
             head: AtomicUsize::new(0),
-            tail: AtomicUsize::new(0),
+            tail: AtomicUsize::new(tail),
+            next: AtomicPtr::new(ptr::null_mut()),
+            #[cfg(feature = "select")]
+            waker: Arc::new(Waker::default()),
+            watermarks: None,
+            overflow: Arc::new(Overflow::new(Policy::Reject)),
+            #[cfg(feature = "seq")]
+            seq: Arc::new(Sequence::new(tail)),
+            #[cfg(feature = "stats")]
+            stats: Arc::new(Counters::default()),
+            #[cfg(feature = "eventfd")]
+            notifier: None,
+        }
+    }
+
+    /// Creates an empty storage of given capacity to continue this one after resize.
+    ///
+    /// The state shared by all storages of the ring buffer as well as memory alignment and allocator are inherited.
+    pub(crate) fn successor(&self, capacity: usize) -> Self {
+        let mut rb = Self::with_alloc(capacity, unsafe { self.data.get_ref() }.alloc().clone());
+        #[cfg(feature = "select")]
+        {
+            rb.waker = self.waker.clone();
+        }
+        rb.watermarks = self.watermarks.clone();
+        rb.overflow = self.overflow.clone();
+        #[cfg(feature = "seq")]
+        {
+            rb.seq = self.seq.clone();
+        }
+        #[cfg(feature = "stats")]
+        {
+            rb.stats = self.stats.clone();
+        }
+        #[cfg(feature = "eventfd")]
+        {
+            rb.notifier = self.notifier.clone();
+        }
+        rb
+    }
+
+    /// Appends the `next` storage after this one.
+    ///
+    /// *Must be called by the producer after the last write to this storage.*
+    pub(crate) fn link(&self, next: Arc<Self>) {
+        let ptr = Arc::into_raw(next) as *mut Self;
+        let prev = self.next.swap(ptr, Ordering::AcqRel);
+        debug_assert!(prev.is_null());
+    }
+
+    /// The storage appended after this one, if any.
+    ///
+    /// *If this storage is empty after the call then it will remain empty forever.*
+    pub(crate) fn next(&self) -> Option<&Self> {
+        unsafe { self.next.load(Ordering::Acquire).as_ref() }
+    }
+
+    /// The owning reference to the storage appended after this one, if any.
+    pub(crate) fn next_arc(&self) -> Option<Arc<Self>> {
+        let ptr = self.next.load(Ordering::Acquire) as *const Self;
+        if ptr.is_null() {
+            None
+        } else {
+            unsafe {
+                Arc::increment_strong_count(ptr);
+                Some(Arc::from_raw(ptr))
+            }
+        }
+    }
+
+    /// Iterates over this storage and all the storages appended after it.
+    pub(crate) fn chain(&self) -> impl Iterator<Item = &Self> {
+        iter::successors(Some(self), |rb| rb.next())
+    }
+
+    /// Appends elements from an iterator to the empty ring buffer.
+    ///
+    /// Elements that haven't been added to the ring buffer remain in the iterator.
+    pub(crate) fn preload<I: Iterator<Item = T>>(self, elems: &mut I) -> Self {
+        let capacity = self.capacity();
+        let data = unsafe { self.data.get_mut() };
+        let mut tail = 0;
+        for (dst, elem) in data[0..capacity].iter_mut().zip(elems) {
+            *dst = MaybeUninit::new(elem);
+            tail += 1;
         }
+        self.tail.store(tail, Ordering::Release);
+        #[cfg(feature = "seq")]
+        self.seq.on_push(tail);
+        self
+    }
+
+    /// Converts the ring buffer into a vector of stored elements.
+    /// The first element of the vector is the oldest one.
+    ///
+    /// The memory of the ring buffer is reused by the vector unless it has custom alignment or allocator.
+    pub fn into_vec(self) -> Vec<T> {
+        let head = self.head.load(Ordering::Acquire);
+        let len = self.len();
+
+        let mut data = mem::take(unsafe { self.data.get_mut() }).into_vec();
+        self.head.store(0, Ordering::Release);
+        self.tail.store(0, Ordering::Release);
+
+        data.rotate_left(head);
+        let mut data = ManuallyDrop::new(data);
+        unsafe { Vec::from_raw_parts(data.as_mut_ptr() as *mut T, len, data.capacity()) }
+    }
+
+    /// Converts the ring buffer into a `VecDeque` of stored elements.
+    /// The front element of the deque is the oldest one.
+    pub fn into_vec_deque(self) -> VecDeque<T> {
+        VecDeque::from(self.into_vec())
     }
 
     /// Splits ring buffer into producer and consumer.
     pub fn split(self) -> (Producer<T>, Consumer<T>) {
         let arc = Arc::new(self);
-        (Producer { rb: arc.clone() }, Consumer { rb: arc })
+        (
+            Producer {
+                rb: arc.clone(),
+                pending: None,
+            },
+            Consumer { rb: arc },
+        )
     }
 
     /// Returns capacity of the ring buffer.
@@ -87,6 +293,59 @@ impl<T: Sized> RingBuffer<T> {
     pub fn remaining(&self) -> usize {
         self.capacity() - self.len()
     }
+
+    /// Returns a snapshot of the ring buffer statistics.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.stats.snapshot()
+    }
+
+    /// Slices of the stored elements, the first one contains older elements.
+    ///
+    /// *The caller must ensure that the elements aren't removed while the slices are in use.*
+    pub(crate) unsafe fn occupied_slices(&self) -> (&[T], &[T]) {
+        let ranges = self.occupied_ranges();
+        let left = &self.data.get_ref()[ranges.0];
+        let right = &self.data.get_ref()[ranges.1];
+        (
+            &*(left as *const [MaybeUninit<T>] as *const [T]),
+            &*(right as *const [MaybeUninit<T>] as *const [T]),
+        )
+    }
+
+    /// Ranges of `data` occupied by the stored elements, the first one contains older elements.
+    pub(crate) fn occupied_ranges(&self) -> (Range<usize>, Range<usize>) {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        let len = unsafe { self.data.get_ref() }.len();
+
+        if head < tail {
+            (head..tail, 0..0)
+        } else if head > tail {
+            (head..len, 0..tail)
+        } else {
+            (0..0, 0..0)
+        }
+    }
+}
+
+impl<T: Sized> FromIterator<T> for RingBuffer<T> {
+    /// Creates a full ring buffer containing elements from the iterator.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let vec = iter.into_iter().collect::<Vec<_>>();
+        let len = vec.len();
+        Self::from_vec(vec, len)
+    }
+}
+
+impl<T: Sized> From<VecDeque<T>> for RingBuffer<T> {
+    /// Creates a full ring buffer containing elements of the deque.
+    /// The front element of the deque will be the first one to be removed from the ring buffer.
+    fn from(deque: VecDeque<T>) -> Self {
+        let vec = Vec::from(deque);
+        let len = vec.len();
+        Self::from_vec(vec, len)
+    }
 }
 
 impl<T: Sized> Drop for RingBuffer<T> {
@@ -102,6 +361,7 @@ impl<T: Sized> Drop for RingBuffer<T> {
         } else {
             (head..len, 0..tail)
         };
+        self.data.touch(&slices);
 
         let drop = |elem_ref: &mut MaybeUninit<T>| unsafe {
             mem::replace(elem_ref, MaybeUninit::uninit()).assume_init();
@@ -112,9 +372,57 @@ impl<T: Sized> Drop for RingBuffer<T> {
         for elem in data[slices.1].iter_mut() {
             drop(elem);
         }
+
+        let next = self.next.load(Ordering::Acquire);
+        if !next.is_null() {
+            mem::drop(unsafe { Arc::from_raw(next) });
+        }
     }
 }
 
+/// Formats elements from consecutive slices as a single list.
+pub(crate) struct DebugElements<'a, T>(pub &'a [&'a [T]]);
+
+impl<'a, T: fmt::Debug> fmt::Debug for DebugElements<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().flat_map(|segment| segment.iter()))
+            .finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, right) = unsafe { self.occupied_slices() };
+        f.debug_struct("RingBuffer")
+            .field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .field("elements", &DebugElements(&[left, right]))
+            .finish()
+    }
+}
+
+impl<T: Clone> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        let (left, right) = unsafe { self.occupied_slices() };
+        let alloc = unsafe { self.data.get_ref() }.alloc().clone();
+        Self::with_alloc(self.capacity(), alloc)
+            .preload(&mut left.iter().chain(right.iter()).cloned())
+    }
+}
+
+impl<T: PartialEq> PartialEq for RingBuffer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let (left, right) = unsafe { self.occupied_slices() };
+        let (other_left, other_right) = unsafe { other.occupied_slices() };
+        left.iter()
+            .chain(right.iter())
+            .eq(other_left.iter().chain(other_right.iter()))
+    }
+}
+
+impl<T: Eq> Eq for RingBuffer<T> {}
+
 struct SlicePtr<T: Sized> {
     pub ptr: *mut T,
     pub len: usize,
@@ -139,49 +447,204 @@ impl<T> SlicePtr<T> {
     }
 }
 
+/// Walks source and destination two-slice views in parallel.
+///
+/// `f` is called with pointers to contiguous segments of source and destination and their common length.
+/// It returns the number of elements it has processed, the walk stops if it is less than the length.
+/// At most `n` elements are walked.
+///
+/// Returns the total number of elements processed.
+unsafe fn zip_segments<A, B, F>(
+    src: (&mut [A], &mut [A]),
+    dst: (&mut [B], &mut [B]),
+    n: usize,
+    mut f: F,
+) -> usize
+where
+    F: FnMut(*mut A, *mut B, usize) -> usize,
+{
+    let mut m = 0;
+    let mut src = (SlicePtr::new(src.0), SlicePtr::new(src.1));
+    let mut dst = (SlicePtr::new(dst.0), SlicePtr::new(dst.1));
+
+    loop {
+        let k = min(n - m, min(src.0.len, dst.0.len));
+        if k == 0 {
+            break;
+        }
+        let l = f(src.0.ptr, dst.0.ptr, k);
+        m += l;
+        if l < k {
+            break;
+        }
+        if src.0.len == k {
+            src.0 = src.1;
+            src.1 = SlicePtr::null();
+        } else {
+            src.0.shift(k);
+        }
+        if dst.0.len == k {
+            dst.0 = dst.1;
+            dst.1 = SlicePtr::null();
+        } else {
+            dst.0.shift(k);
+        }
+    }
+
+    m
+}
+
 /// Moves at most `count` items from the `src` consumer to the `dst` producer.
 /// Consumer and producer may be of different buffers as well as of the same one.
 ///
+/// Any types implementing [`RbConsumer`](trait.RbConsumer.html) and [`RbProducer`](trait.RbProducer.html) may be used.
+///
 /// `count` is the number of items being moved, if `None` - as much as possible items will be moved.
 ///
 /// Returns number of items been moved.
-pub fn move_items<T>(src: &mut Consumer<T>, dst: &mut Producer<T>, count: Option<usize>) -> usize {
-    unsafe {
-        src.pop_access(|src_left, src_right| -> usize {
-            dst.push_access(|dst_left, dst_right| -> usize {
-                let n = count.unwrap_or_else(|| {
-                    min(
-                        src_left.len() + src_right.len(),
-                        dst_left.len() + dst_right.len(),
+pub fn move_items<T, C, P>(src: &mut C, dst: &mut P, count: Option<usize>) -> usize
+where
+    C: RbConsumer<T>,
+    P: RbProducer<T>,
+{
+    // The source may consist of several storages after resize, so the items are moved in several passes.
+    // The limit is taken in advance to stop if the source and the destination are the same ring buffer.
+    let limit = min(count.unwrap_or(usize::MAX), min(src.len(), dst.remaining()));
+    let mut total = 0;
+    while total < limit {
+        let n = unsafe {
+            src.pop_access(|src_left, src_right| -> usize {
+                dst.push_access(|dst_left, dst_right| -> usize {
+                    zip_segments(
+                        (src_left, src_right),
+                        (dst_left, dst_right),
+                        limit - total,
+                        |s, d, k| {
+                            copy(s, d, k);
+                            k
+                        },
                     )
+                })
+            })
+        };
+        if n == 0 {
+            break;
+        }
+        total += n;
+    }
+    total
+}
+
+/// Removes at most `count` items from the `src` consumer, converts them by `f` and appends to the `dst` producer.
+///
+/// Any types implementing [`RbConsumer`](trait.RbConsumer.html) and [`RbProducer`](trait.RbProducer.html) may be used.
+///
+/// `count` is the number of items being moved, if `None` - as much as possible items will be moved.
+///
+/// If `f` panics then the items converted before are kept in `dst`
+/// and the item being converted is removed from `src`.
+///
+/// Returns number of items been moved.
+pub fn move_map<A, B, C, P, F>(src: &mut C, dst: &mut P, count: Option<usize>, mut f: F) -> usize
+where
+    C: RbConsumer<A>,
+    P: RbProducer<B>,
+    F: FnMut(A) -> B,
+{
+    let limit = min(count.unwrap_or(usize::MAX), min(src.len(), dst.remaining()));
+    let mut total = 0;
+    while total < limit {
+        // The traits commit only on return from the access closures,
+        // so a panic is caught once per pass and resumed after the converted items are committed.
+        let mut payload = None;
+        let n = unsafe {
+            src.pop_access(|src_left, src_right| -> usize {
+                let mut taken = 0;
+                dst.push_access(|dst_left, dst_right| -> usize {
+                    let mut written = 0;
+                    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                        zip_segments(
+                            (src_left, src_right),
+                            (dst_left, dst_right),
+                            limit - total,
+                            |s, d, k| {
+                                for i in 0..k {
+                                    let a = s.add(i).read().assume_init();
+                                    taken += 1;
+                                    d.add(i).write(MaybeUninit::new(f(a)));
+                                    written += 1;
+                                }
+                                k
+                            },
+                        )
+                    }));
+                    payload = res.err();
+                    written
                 });
-                let mut m = 0;
-                let mut src = (SlicePtr::new(src_left), SlicePtr::new(src_right));
-                let mut dst = (SlicePtr::new(dst_left), SlicePtr::new(dst_right));
-
-                loop {
-                    let k = min(n - m, min(src.0.len, dst.0.len));
-                    if k == 0 {
-                        break;
-                    }
-                    copy(src.0.ptr, dst.0.ptr, k);
-                    if src.0.len == k {
-                        src.0 = src.1;
-                        src.1 = SlicePtr::null();
-                    } else {
-                        src.0.shift(k);
-                    }
-                    if dst.0.len == k {
-                        dst.0 = dst.1;
-                        dst.1 = SlicePtr::null();
-                    } else {
-                        dst.0.shift(k);
-                    }
-                    m += k
-                }
-
-                m
+                taken
             })
-        })
+        };
+        if let Some(e) = payload {
+            panic::resume_unwind(e);
+        }
+        if n == 0 {
+            break;
+        }
+        total += n;
+    }
+    total
+}
+
+/// Removes at most `count` items from the `src` consumer, converts them in batches by `f` and appends to the `dst` producer.
+///
+/// `f` takes a slice of source items and a slice of the same length to write converted items to.
+/// It is called for each pair of contiguous segments of source and destination
+/// (at most three times per storage of the source).
+///
+/// `count` is the number of items being moved, if `None` - as much as possible items will be moved.
+///
+/// If `f` panics nothing is moved by the pass being made.
+///
+/// Returns number of items been moved.
+///
+/// # Safety
+///
+/// `f` must initialize all items of the destination slice.
+pub unsafe fn move_map_slices<A, B, C, P, F>(
+    src: &mut C,
+    dst: &mut P,
+    count: Option<usize>,
+    mut f: F,
+) -> usize
+where
+    A: Copy,
+    C: RbConsumer<A>,
+    P: RbProducer<B>,
+    F: FnMut(&[A], &mut [MaybeUninit<B>]),
+{
+    let limit = min(count.unwrap_or(usize::MAX), min(src.len(), dst.remaining()));
+    let mut total = 0;
+    while total < limit {
+        let n = src.pop_access(|src_left, src_right| -> usize {
+            dst.push_access(|dst_left, dst_right| -> usize {
+                zip_segments(
+                    (src_left, src_right),
+                    (dst_left, dst_right),
+                    limit - total,
+                    |s, d, k| {
+                        f(
+                            slice::from_raw_parts(s as *const A, k),
+                            slice::from_raw_parts_mut(d, k),
+                        );
+                        k
+                    },
+                )
+            })
+        });
+        if n == 0 {
+            break;
+        }
+        total += n;
     }
+    total
 }
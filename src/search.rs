@@ -0,0 +1,121 @@
+use std::{cmp::min, mem};
+
+use crate::consumer::Consumer;
+
+/// Word with every byte equal to one.
+const LO: usize = usize::MAX / 0xFF;
+/// Word with the highest bit of every byte set.
+const HI: usize = LO << 7;
+
+/// Returns the index of the first occurrence of `byte` in `haystack`.
+///
+/// Compares a word of bytes at once.
+fn memchr(byte: u8, haystack: &[u8]) -> Option<usize> {
+    let (prefix, words, suffix) = unsafe { haystack.align_to::<usize>() };
+    if let Some(i) = prefix.iter().position(|&b| b == byte) {
+        return Some(i);
+    }
+    let pattern = LO * byte as usize;
+    let start = match words.iter().position(|&word| {
+        let x = word ^ pattern;
+        // Nonzero if any byte of `x` is zero.
+        x.wrapping_sub(LO) & !x & HI != 0
+    }) {
+        Some(k) => prefix.len() + k * mem::size_of::<usize>(),
+        None => haystack.len() - suffix.len(),
+    };
+    haystack[start..]
+        .iter()
+        .position(|&b| b == byte)
+        .map(|i| start + i)
+}
+
+/// Returns the index of the first occurrence of `byte` not before `from` in concatenation of `segments`.
+fn find_byte_from(segments: &[&[u8]], byte: u8, from: usize) -> Option<usize> {
+    let mut start = 0;
+    for segment in segments {
+        if from < start + segment.len() {
+            let skip = from.saturating_sub(start);
+            if let Some(i) = memchr(byte, &segment[skip..]) {
+                return Some(start + skip + i);
+            }
+        }
+        start += segment.len();
+    }
+    None
+}
+
+/// Returns the index of the first occurrence of `needle` in concatenation of `segments`.
+fn find_subslice(segments: &[&[u8]], needle: &[u8]) -> Option<usize> {
+    let (&first, rest) = match needle.split_first() {
+        Some(split) => split,
+        None => return Some(0),
+    };
+    let len = segments.iter().map(|segment| segment.len()).sum::<usize>();
+    let at = |mut i: usize| {
+        for segment in segments {
+            if i < segment.len() {
+                return segment[i];
+            }
+            i -= segment.len();
+        }
+        unreachable!()
+    };
+    let mut from = 0;
+    while from + needle.len() <= len {
+        let i = find_byte_from(segments, first, from)?;
+        if i + needle.len() > len {
+            break;
+        }
+        if rest.iter().enumerate().all(|(k, &b)| at(i + 1 + k) == b) {
+            return Some(i);
+        }
+        from = i + 1;
+    }
+    None
+}
+
+impl Consumer<u8> {
+    /// Stored bytes split into segments, including the ones in storages left after resize.
+    ///
+    /// # Safety
+    ///
+    /// The ring buffer must be locked or pinned while the segments are used.
+    unsafe fn segments(&self) -> Vec<&[u8]> {
+        self.chain_slices()
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    }
+
+    /// Returns the index of the first stored byte equal to `byte` without removing any bytes.
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        let _lock = self.lock();
+        find_byte_from(&unsafe { self.segments() }, byte, 0)
+    }
+
+    /// Returns the index of the first occurrence of `needle` in the stored bytes without removing any bytes.
+    ///
+    /// The occurrence may straddle the wrap point of the ring buffer.
+    pub fn find_subslice(&self, needle: &[u8]) -> Option<usize> {
+        let _lock = self.lock();
+        find_subslice(&unsafe { self.segments() }, needle)
+    }
+
+    /// Removes bytes up to and including the first occurrence of `delim` and appends them to `buffer`.
+    ///
+    /// Returns the number of bytes been removed
+    /// or `None` if there is no `delim` in the ring buffer, in that case nothing is removed.
+    pub fn read_until_delim(&mut self, delim: &[u8], buffer: &mut Vec<u8>) -> Option<usize> {
+        // Keeps the producer from overwriting the bytes between the search and the removal.
+        let _lock = self.lock_mut();
+        let segments = unsafe { self.segments() };
+        let n = find_subslice(&segments, delim)? + delim.len();
+        let mut rest = n;
+        for segment in segments {
+            let l = min(rest, segment.len());
+            buffer.extend_from_slice(&segment[..l]);
+            rest -= l;
+        }
+        Some(self.discard(n))
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    consumer::Consumer,
+    ring_buffer::RingBuffer,
+    sync::{fence, Arc, Ordering},
+    waker::Waker,
+};
+
+/// Type-erased view of a ring buffer observed by `Select`.
+trait Watch {
+    fn has_data(&self) -> bool;
+    fn waker(&self) -> &Waker;
+}
+
+impl<T> Watch for Arc<RingBuffer<T>> {
+    fn has_data(&self) -> bool {
+        // The consumer may have switched to a newer storage after resize.
+        self.chain().any(|rb| !rb.is_empty())
+    }
+
+    fn waker(&self) -> &Waker {
+        &self.waker
+    }
+}
+
+/// Waits until any of several consumers has some data.
+///
+/// Consumers of different element types can be added, each of them gets an index in order of addition.
+/// The selecting thread is parked while all the consumers are empty
+/// and is woken up by the producers when they append elements, so there is no busy waiting.
+///
+/// Only one `Select` should wait on the same consumer at a time.
+///
+/// Available only with `select` feature enabled,
+/// otherwise the producer doesn't have to check for a selecting thread on every push.
+///
+/// ```rust
+/// # extern crate ringbuf;
+/// # use ringbuf::{RingBuffer, Select};
+/// # use std::thread;
+/// # fn main() {
+/// let (mut prod_a, mut cons_a) = RingBuffer::<i32>::new(2).split();
+/// let (mut prod_b, mut cons_b) = RingBuffer::<char>::new(2).split();
+///
+/// let pjh = thread::spawn(move || {
+///     prod_b.push('b').unwrap();
+///     prod_a
+/// });
+///
+/// let mut select = Select::new();
+/// let a = select.add(&cons_a);
+/// let b = select.add(&cons_b);
+/// assert_eq!(a, 0);
+/// assert_eq!(select.ready(), b);
+/// assert_eq!(cons_b.pop(), Some('b'));
+///
+/// let mut prod_a = pjh.join().unwrap();
+/// prod_a.push(1).unwrap();
+/// assert_eq!(select.ready(), a);
+/// assert_eq!(cons_a.pop(), Some(1));
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Select<'a> {
+    entries: Vec<Box<dyn Watch + 'a>>,
+    /// Index to start the next scan from, so that busy consumers don't starve others.
+    start: usize,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty set of consumers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a consumer to the set and returns its index.
+    ///
+    /// The consumer isn't borrowed, so elements can be removed from it between waits.
+    pub fn add<T: 'a>(&mut self, cons: &Consumer<T>) -> usize {
+        self.entries.push(Box::new(cons.rb.clone()));
+        self.entries.len() - 1
+    }
+
+    /// Blocks until at least one of the consumers isn't empty and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no consumers added.
+    pub fn ready(&mut self) -> usize {
+        assert!(!self.entries.is_empty(), "no consumers to select from");
+        self.wait(None).unwrap()
+    }
+
+    /// Blocks until at least one of the consumers isn't empty or `timeout` elapses.
+    ///
+    /// Returns index of a non-empty consumer or `None` on timeout.
+    pub fn ready_timeout(&mut self, timeout: Duration) -> Option<usize> {
+        self.wait(Some(Instant::now() + timeout))
+    }
+
+    /// Returns index of a non-empty consumer without blocking.
+    pub fn try_ready(&mut self) -> Option<usize> {
+        let count = self.entries.len();
+        let index = (0..count)
+            .map(|i| (self.start + i) % count)
+            .find(|&i| self.entries[i].has_data())?;
+        self.start = (index + 1) % count;
+        Some(index)
+    }
+
+    fn wait(&mut self, deadline: Option<Instant>) -> Option<usize> {
+        if let Some(index) = self.try_ready() {
+            return Some(index);
+        }
+        for entry in &self.entries {
+            entry.waker().register();
+        }
+        // Pairs with the fence in `Waker::wake`: either the producer sees the waker armed
+        // or the check below sees the elements it has appended.
+        fence(Ordering::SeqCst);
+        let result = loop {
+            if let Some(index) = self.try_ready() {
+                break Some(index);
+            }
+            match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break None;
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+                None => thread::park(),
+            }
+        };
+        for entry in &self.entries {
+            entry.waker().unregister();
+        }
+        result
+    }
+}
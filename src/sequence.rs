@@ -0,0 +1,112 @@
+use std::convert::TryFrom;
+
+use crate::{
+    consumer::Consumer,
+    policy::Policy,
+    producer::Producer,
+    sync::{AtomicU64, Ordering},
+    traits::*,
+};
+
+/// Counter occupying a cache line of its own, so updates by one side don't slow down the other.
+#[repr(align(64))]
+struct Counter(AtomicU64);
+
+impl Counter {
+    fn load(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Increments the counter, there must be no concurrent writers.
+    fn add(&self, n: usize) {
+        self.0
+            .store(self.0.load(Ordering::Relaxed) + n as u64, Ordering::Release);
+    }
+}
+
+/// Numbers of elements ever appended to and removed from the ring buffer, shared by all its storages.
+///
+/// The sequence number of an element is the number of elements appended before it,
+/// so the oldest stored element has the number equal to `popped`.
+pub(crate) struct Sequence {
+    /// Updated by the producer only.
+    pushed: Counter,
+    /// Updated by the consumer or by the producer evicting elements while the consumer is locked out.
+    popped: Counter,
+}
+
+impl Sequence {
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            pushed: Counter(AtomicU64::new(len as u64)),
+            popped: Counter(AtomicU64::new(0)),
+        }
+    }
+
+    pub(crate) fn on_push(&self, n: usize) {
+        self.pushed.add(n);
+    }
+
+    pub(crate) fn on_pop(&self, n: usize) {
+        self.popped.add(n);
+    }
+}
+
+impl<T: Sized> Producer<T> {
+    /// Sequence number the next appended element will get.
+    ///
+    /// Available only with `seq` feature enabled.
+    pub fn next_seq(&self) -> u64 {
+        self.rb.seq.pushed.load()
+    }
+
+    /// Appends an element to the ring buffer and returns its sequence number.
+    /// On failure returns an error containing the element that hasn't beed appended.
+    ///
+    /// Unlike [`push`](#method.push) the method doesn't drop the element with `DropNewest` policy
+    /// but returns it as with `Reject` one.
+    pub fn push_with_seq(&mut self, elem: T) -> Result<u64, T> {
+        let seq = self.next_seq();
+        if self.policy() == Policy::DropNewest {
+            RbProducer::push(self, elem)
+        } else {
+            self.push(elem)
+        }
+        .map(|()| seq)
+    }
+}
+
+impl<T: Sized> Consumer<T> {
+    /// Sequence number of the next element to be removed.
+    ///
+    /// The number increases by more than one on removal if the producer has overwritten elements,
+    /// so gaps can be detected by comparing it with the number of the last removed element.
+    ///
+    /// Available only with `seq` feature enabled.
+    pub fn next_seq(&self) -> u64 {
+        self.rb.seq.popped.load()
+    }
+
+    /// Removes the oldest element from the ring buffer and returns it along with its sequence number.
+    /// Returns `None` if the ring buffer is empty.
+    pub fn pop_with_seq(&mut self) -> Option<(u64, T)> {
+        // Keeps the producer from overwriting the element between the calls below.
+        let _lock = self.lock_mut();
+        let seq = self.next_seq();
+        self.pop().map(|elem| (seq, elem))
+    }
+
+    /// Returns a reference to the stored element with given sequence number.
+    /// Returns `None` if the element has been removed or hasn't been appended yet.
+    pub fn get_by_seq(&self, seq: u64) -> Option<&T> {
+        let lock = self.lock();
+        let index = usize::try_from(seq.checked_sub(self.next_seq())?).ok()?;
+        // The returned reference outlives the lock, so the elements are pinned as by `as_slices`.
+        if let Some(lock) = &lock {
+            lock.pin();
+        }
+        unsafe { self.chain_slices() }
+            .flat_map(|segment| segment.iter())
+            .nth(index)
+    }
+}
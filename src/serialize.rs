@@ -0,0 +1,206 @@
+use std::{cmp::max, fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{Serialize, SerializeSeq, SerializeStruct, Serializer},
+};
+
+use crate::{consumer::Consumer, ring_buffer::RingBuffer};
+
+/// Serializable view of the ring buffer contents.
+///
+/// Serialized in the same way as [`RingBuffer`](struct.RingBuffer.html):
+/// as the capacity and the ordered sequence of stored elements.
+/// So it can be deserialized into `RingBuffer` containing the same elements.
+///
+/// Available only with `serde` feature enabled.
+pub struct Snapshot<'a, T> {
+    capacity: usize,
+    segments: Vec<&'a [T]>,
+}
+
+impl<'a, T> Snapshot<'a, T> {
+    /// Creates a snapshot from ring buffer capacity and two slices of its contents
+    /// as given by [`Consumer::access`](struct.Consumer.html#method.access).
+    pub fn new(capacity: usize, left: &'a [T], right: &'a [T]) -> Self {
+        Self {
+            capacity,
+            segments: vec![left, right],
+        }
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Makes a serializable snapshot of the elements contained by the ring buffer without removing them.
+    ///
+    /// If the producer has resized the buffer then the snapshot includes the elements of all its storages
+    /// and its capacity is increased to the number of elements if they don't fit into the current capacity.
+    ///
+    /// *The snapshot may not include elements pushed to the buffer by concurring producer after the method call.*
+    pub fn snapshot(&self) -> Snapshot<'_, T> {
+        let lock = self.lock();
+        // The snapshot borrows the elements, so they are pinned as by `as_slices`.
+        if let Some(lock) = &lock {
+            lock.pin();
+        }
+        let segments = unsafe { self.chain_slices() }.collect::<Vec<_>>();
+        let len = segments.iter().map(|segment| segment.len()).sum();
+        Snapshot {
+            capacity: max(self.capacity(), len),
+            segments,
+        }
+    }
+}
+
+struct Elements<'a, T>(&'a [&'a [T]]);
+
+impl<'a, T: Serialize> Serialize for Elements<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = self.0.iter().map(|segment| segment.len()).sum();
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for elem in self.0.iter().flat_map(|segment| segment.iter()) {
+            seq.serialize_element(elem)?;
+        }
+        seq.end()
+    }
+}
+
+fn serialize_contents<S, T>(
+    serializer: S,
+    capacity: usize,
+    segments: &[&[T]],
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut state = serializer.serialize_struct("RingBuffer", 2)?;
+    state.serialize_field("capacity", &capacity)?;
+    state.serialize_field("elements", &Elements(segments))?;
+    state.end()
+}
+
+impl<'a, T: Serialize> Serialize for Snapshot<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_contents(serializer, self.capacity, &self.segments)
+    }
+}
+
+impl<T: Serialize> Serialize for RingBuffer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (left, right) = unsafe { self.occupied_slices() };
+        serialize_contents(serializer, self.capacity(), &[left, right])
+    }
+}
+
+const FIELDS: &[&str] = &["capacity", "elements"];
+
+enum Field {
+    Capacity,
+    Elements,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("`capacity` or `elements`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                match value {
+                    "capacity" => Ok(Field::Capacity),
+                    "elements" => Ok(Field::Elements),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct RingBufferVisitor<T> {
+    phantom: PhantomData<T>,
+}
+
+impl<T> RingBufferVisitor<T> {
+    fn build<E: de::Error>(capacity: usize, mut elements: Vec<T>) -> Result<RingBuffer<T>, E> {
+        if elements.len() > capacity {
+            return Err(de::Error::invalid_length(
+                elements.len(),
+                &"number of elements not exceeding capacity",
+            ));
+        }
+        // The capacity comes from untrusted input, so the memory is reserved fallibly instead of aborting.
+        let reserved = match capacity.checked_add(1) {
+            Some(size) => elements.try_reserve_exact(size - elements.len()).is_ok(),
+            None => false,
+        };
+        if !reserved {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(capacity as u64),
+                &"capacity of ring buffer that can be allocated",
+            ));
+        }
+        Ok(RingBuffer::from_vec(elements, capacity))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for RingBufferVisitor<T> {
+    type Value = RingBuffer<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("struct RingBuffer")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let capacity = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let elements = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Self::build(capacity, elements)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut capacity = None;
+        let mut elements = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Capacity => {
+                    if capacity.is_some() {
+                        return Err(de::Error::duplicate_field("capacity"));
+                    }
+                    capacity = Some(map.next_value()?);
+                }
+                Field::Elements => {
+                    if elements.is_some() {
+                        return Err(de::Error::duplicate_field("elements"));
+                    }
+                    elements = Some(map.next_value()?);
+                }
+            }
+        }
+        let capacity = capacity.ok_or_else(|| de::Error::missing_field("capacity"))?;
+        let elements = elements.ok_or_else(|| de::Error::missing_field("elements"))?;
+        Self::build(capacity, elements)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for RingBuffer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct(
+            "RingBuffer",
+            FIELDS,
+            RingBufferVisitor {
+                phantom: PhantomData,
+            },
+        )
+    }
+}
@@ -0,0 +1,65 @@
+use crate::sync::{AtomicU64, AtomicUsize, Ordering};
+
+/// Snapshot of ring buffer statistics.
+///
+/// Available only with `stats` feature enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Total number of elements pushed to the buffer.
+    pub pushed: u64,
+    /// Total number of elements popped from the buffer, including ones dropped by `Policy::OverwriteOldest`.
+    pub popped: u64,
+    /// Number of push attempts that have rejected or dropped elements because the buffer was full.
+    pub push_failures: u64,
+    /// Number of pop attempts failed because the buffer was empty.
+    pub pop_failures: u64,
+    /// Maximal number of elements ever stored in the buffer at once (high watermark).
+    pub peak_len: usize,
+    /// Number of times the producer has wrapped around the end of the buffer memory.
+    pub wraps: u64,
+}
+
+/// Counters updated by producer and consumer.
+#[derive(Default)]
+pub(crate) struct Counters {
+    pushed: AtomicU64,
+    popped: AtomicU64,
+    push_failures: AtomicU64,
+    pop_failures: AtomicU64,
+    peak_len: AtomicUsize,
+    wraps: AtomicU64,
+}
+
+impl Counters {
+    /// Registers `n` elements been pushed, `len` is the length of data in the buffer after that.
+    pub fn on_push(&self, n: usize, len: usize, wrapped: bool) {
+        self.pushed.fetch_add(n as u64, Ordering::Relaxed);
+        self.peak_len.fetch_max(len, Ordering::Relaxed);
+        if wrapped {
+            self.wraps.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn on_pop(&self, n: usize) {
+        self.popped.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    pub fn on_push_failure(&self) {
+        self.push_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn on_pop_failure(&self) {
+        self.pop_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> Stats {
+        Stats {
+            pushed: self.pushed.load(Ordering::Relaxed),
+            popped: self.popped.load(Ordering::Relaxed),
+            push_failures: self.push_failures.load(Ordering::Relaxed),
+            pop_failures: self.pop_failures.load(Ordering::Relaxed),
+            peak_len: self.peak_len.load(Ordering::Relaxed),
+            wraps: self.wraps.load(Ordering::Relaxed),
+        }
+    }
+}
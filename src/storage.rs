@@ -0,0 +1,170 @@
+#[cfg(feature = "allocator_api")]
+use std::{alloc::Allocator, sync::Arc};
+use std::{
+    alloc::{self, Layout},
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+    slice,
+};
+
+/// Alignment and allocator of the storage memory.
+#[derive(Clone)]
+pub(crate) struct Alloc {
+    /// Minimal alignment, the natural alignment of elements is used if it is greater.
+    pub(crate) align: usize,
+    /// Allocator of the memory, the global one if `None`.
+    #[cfg(feature = "allocator_api")]
+    pub(crate) allocator: Option<Arc<dyn Allocator + Send + Sync>>,
+}
+
+impl Default for Alloc {
+    fn default() -> Self {
+        Self {
+            align: 1,
+            #[cfg(feature = "allocator_api")]
+            allocator: None,
+        }
+    }
+}
+
+impl Alloc {
+    /// Layout of the memory for `len` elements.
+    fn layout<T>(&self, len: usize) -> Layout {
+        Layout::array::<T>(len)
+            .and_then(|layout| layout.align_to(self.align))
+            .expect("capacity overflow")
+    }
+
+    /// Checks if the memory is allocated the same way as the memory of `Vec<T>`.
+    fn is_default<T>(&self) -> bool {
+        #[cfg(feature = "allocator_api")]
+        if self.allocator.is_some() {
+            return false;
+        }
+        self.align <= mem::align_of::<T>()
+    }
+
+    fn allocate(&self, layout: Layout) -> NonNull<u8> {
+        if layout.size() == 0 {
+            // Aligned dangling pointer.
+            return unsafe { NonNull::new_unchecked(layout.align() as *mut u8) };
+        }
+        #[cfg(feature = "allocator_api")]
+        if let Some(allocator) = &self.allocator {
+            return match allocator.allocate(layout) {
+                Ok(ptr) => ptr.cast(),
+                Err(_) => alloc::handle_alloc_error(layout),
+            };
+        }
+        match NonNull::new(unsafe { alloc::alloc(layout) }) {
+            Some(ptr) => ptr,
+            None => alloc::handle_alloc_error(layout),
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            return;
+        }
+        #[cfg(feature = "allocator_api")]
+        if let Some(allocator) = &self.allocator {
+            allocator.deallocate(ptr, layout);
+            return;
+        }
+        alloc::dealloc(ptr.as_ptr(), layout);
+    }
+}
+
+/// Memory of the ring buffer, a fixed number of possibly uninitialized element slots.
+///
+/// Elements are not dropped along with the storage.
+pub(crate) struct Storage<T> {
+    ptr: NonNull<MaybeUninit<T>>,
+    len: usize,
+    /// Number of slots the memory has been allocated for, may exceed `len` if taken from a vector.
+    cap: usize,
+    alloc: Alloc,
+}
+
+unsafe impl<T: Send> Send for Storage<T> {}
+unsafe impl<T: Sync> Sync for Storage<T> {}
+
+impl<T> Storage<T> {
+    /// Allocates `len` uninitialized slots.
+    pub(crate) fn new(len: usize, alloc: Alloc) -> Self {
+        let ptr = alloc.allocate(alloc.layout::<T>(len)).cast();
+        Self {
+            ptr,
+            len,
+            cap: len,
+            alloc,
+        }
+    }
+
+    /// Reuses the memory of the vector, slots are its elements.
+    pub(crate) fn from_vec(vec: Vec<MaybeUninit<T>>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(vec.as_mut_ptr()) },
+            len: vec.len(),
+            cap: vec.capacity(),
+            alloc: Alloc::default(),
+        }
+    }
+
+    /// Converts the storage into a vector of its slots.
+    ///
+    /// The memory is reused if it has default alignment and allocator, otherwise the slots are copied.
+    pub(crate) fn into_vec(self) -> Vec<MaybeUninit<T>> {
+        let this = ManuallyDrop::new(self);
+        if this.alloc.is_default::<T>() {
+            unsafe { Vec::from_raw_parts(this.ptr.as_ptr(), this.len, this.cap) }
+        } else {
+            let mut vec = Vec::with_capacity(this.len);
+            unsafe {
+                ptr::copy_nonoverlapping(this.ptr.as_ptr(), vec.as_mut_ptr(), this.len);
+                vec.set_len(this.len);
+                this.deallocate();
+            }
+            vec
+        }
+    }
+
+    /// Alignment and allocator of the memory.
+    pub(crate) fn alloc(&self) -> &Alloc {
+        &self.alloc
+    }
+
+    /// *Must be called once.*
+    unsafe fn deallocate(&self) {
+        self.alloc
+            .deallocate(self.ptr.cast(), self.alloc.layout::<T>(self.cap));
+    }
+}
+
+impl<T> Default for Storage<T> {
+    fn default() -> Self {
+        Self::new(0, Alloc::default())
+    }
+}
+
+impl<T> Deref for Storage<T> {
+    type Target = [MaybeUninit<T>];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for Storage<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for Storage<T> {
+    fn drop(&mut self) {
+        unsafe { self.deallocate() };
+    }
+}
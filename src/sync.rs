@@ -0,0 +1,24 @@
+//! Synchronization primitives used by the crate.
+//!
+//! When built with `--cfg loom` they are replaced by the ones from [`loom`](https://docs.rs/loom)
+//! to check the head/tail protocol against all possible thread interleavings.
+
+#[cfg(loom)]
+pub(crate) use loom::thread::yield_now as spin_loop;
+#[cfg(loom)]
+pub(crate) use loom::sync::{
+    atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+#[cfg(all(loom, any(feature = "seq", feature = "stats")))]
+pub(crate) use loom::sync::atomic::AtomicU64;
+
+#[cfg(not(loom))]
+pub(crate) use std::hint::spin_loop;
+#[cfg(not(loom))]
+pub(crate) use std::sync::{
+    atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+#[cfg(all(not(loom), any(feature = "seq", feature = "stats")))]
+pub(crate) use std::sync::atomic::AtomicU64;
@@ -0,0 +1,54 @@
+use std::{cell::Cell, sync::Mutex, thread};
+
+use crate::{Consumer, Producer, RingBuffer};
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn send_sync() {
+    assert_send::<RingBuffer<i32>>();
+    assert_sync::<RingBuffer<i32>>();
+    assert_send::<Producer<i32>>();
+    assert_sync::<Producer<i32>>();
+    assert_send::<Consumer<i32>>();
+    assert_sync::<Consumer<i32>>();
+
+    // Elements that can be sent but not shared.
+    assert_send::<RingBuffer<Cell<i32>>>();
+    assert_send::<Producer<Cell<i32>>>();
+    assert_sync::<Producer<Cell<i32>>>();
+    assert_send::<Consumer<Cell<i32>>>();
+
+    // Elements that can be shared but not sent.
+    assert_sync::<RingBuffer<std::sync::MutexGuard<'static, i32>>>();
+}
+
+#[test]
+fn cell() {
+    let (mut prod, mut cons) = RingBuffer::<Cell<i32>>::new(2).split();
+
+    let pjh = thread::spawn(move || {
+        prod.push(Cell::new(1)).unwrap();
+    });
+    pjh.join().unwrap();
+
+    let cjh = thread::spawn(move || {
+        let cell = cons.pop().unwrap();
+        cell.set(cell.get() + 1);
+        cell.get()
+    });
+    assert_eq!(cjh.join().unwrap(), 2);
+}
+
+#[test]
+fn shared_consumer() {
+    let (mut prod, cons) = RingBuffer::<Mutex<i32>>::new(2).split();
+    prod.push(Mutex::new(1)).unwrap();
+
+    let cons = &cons;
+    thread::scope(|s| {
+        s.spawn(move || *cons.iter().next().unwrap().lock().unwrap() += 1);
+    });
+    assert_eq!(*cons.iter().next().unwrap().lock().unwrap(), 2);
+}
@@ -0,0 +1,105 @@
+use std::{mem::MaybeUninit, sync::atomic::Ordering};
+
+use crate::RingBuffer;
+
+fn is_aligned<T>(slice: &[T], align: usize) -> bool {
+    slice.as_ptr() as usize & (align - 1) == 0
+}
+
+#[test]
+fn align() {
+    for &align in [1, 16, 64, 4096].iter() {
+        let rb = RingBuffer::<f32>::builder(15).align(align).build();
+        assert_eq!(rb.capacity(), 15);
+        assert!(is_aligned(unsafe { rb.data.get_ref() }, align));
+        assert!(is_aligned(unsafe { rb.clone().data.get_ref() }, align));
+    }
+}
+
+#[test]
+#[should_panic]
+fn align_not_power_of_two() {
+    RingBuffer::<u8>::builder(1).align(48);
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::builder(3).align(64).build().split();
+    prod.push_slice(&[1, 2, 3]);
+    prod.resize(8);
+    prod.push_slice(&[4, 5]);
+
+    let mut elems = [0; 5];
+    assert_eq!(cons.pop_slice(&mut elems), 5);
+    assert_eq!(elems, [1, 2, 3, 4, 5]);
+    assert!(is_aligned(unsafe { cons.rb.data.get_ref() }, 64));
+}
+
+#[test]
+fn into_vec() {
+    let rb = RingBuffer::<String>::builder(3).align(32).build();
+    {
+        let data = unsafe { rb.data.get_mut() };
+        data[2] = MaybeUninit::new("a".to_string());
+        data[3] = MaybeUninit::new("b".to_string());
+        data[0] = MaybeUninit::new("c".to_string());
+    }
+    rb.head.store(2, Ordering::Release);
+    rb.tail.store(1, Ordering::Release);
+
+    assert_eq!(rb.into_vec(), ["a", "b", "c"]);
+}
+
+#[test]
+fn zero_sized() {
+    let (mut prod, mut cons) = RingBuffer::<()>::builder(2).align(64).build().split();
+    prod.push(()).unwrap();
+    prod.push(()).unwrap();
+    assert_eq!(prod.push(()), Err(()));
+    assert_eq!(cons.pop(), Some(()));
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn allocator() {
+    use std::{
+        alloc::{AllocError, Allocator, Global, Layout},
+        ptr::NonNull,
+        sync::atomic::{AtomicIsize, Ordering},
+    };
+
+    static ALLOCATED: AtomicIsize = AtomicIsize::new(0);
+
+    struct Counting;
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            ALLOCATED.fetch_add(layout.size() as isize, Ordering::SeqCst);
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            ALLOCATED.fetch_sub(layout.size() as isize, Ordering::SeqCst);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    let (mut prod, mut cons) = RingBuffer::<u32>::builder(3)
+        .align(64)
+        .allocator(Counting)
+        .build()
+        .split();
+    assert_eq!(ALLOCATED.load(Ordering::SeqCst), 16);
+    prod.push_slice(&[1, 2, 3]);
+    prod.resize(7);
+    assert_eq!(ALLOCATED.load(Ordering::SeqCst), 16 + 32);
+    prod.push(4).unwrap();
+
+    let mut elems = [0; 4];
+    assert_eq!(cons.pop_slice(&mut elems), 4);
+    assert_eq!(elems, [1, 2, 3, 4]);
+    // The drained storage is released by the producer on the next push.
+    prod.push(5).unwrap();
+    assert_eq!(ALLOCATED.load(Ordering::SeqCst), 32);
+    drop((prod, cons));
+    assert_eq!(ALLOCATED.load(Ordering::SeqCst), 0);
+}
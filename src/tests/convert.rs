@@ -0,0 +1,70 @@
+use std::{collections::VecDeque, mem::MaybeUninit, sync::atomic::Ordering};
+
+use crate::RingBuffer;
+
+#[test]
+fn from_vec() {
+    let mut vec = Vec::with_capacity(5);
+    vec.extend_from_slice(&[1, 2, 3]);
+    let ptr = vec.as_ptr();
+
+    let rb = RingBuffer::from_vec(vec, 4);
+    assert_eq!(rb.capacity(), 4);
+    assert_eq!(rb.len(), 3);
+    assert_eq!(unsafe { rb.data.get_ref() }.as_ptr() as *const i32, ptr);
+
+    let (mut prod, mut cons) = rb.split();
+    assert_eq!(prod.push_slice(&[4, 5]), 1);
+    let mut tmp = [0; 5];
+    assert_eq!(cons.pop_slice(&mut tmp), 4);
+    assert_eq!(tmp[0..4], [1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic]
+fn from_vec_overflow() {
+    RingBuffer::from_vec(vec![1, 2, 3], 2);
+}
+
+#[test]
+fn from_iter() {
+    let rb = (0..4).collect::<RingBuffer<i32>>();
+    assert_eq!(rb.capacity(), 4);
+    assert!(rb.is_full());
+    assert_eq!(rb.into_vec(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn from_vec_deque() {
+    let mut deque = VecDeque::with_capacity(4);
+    deque.push_back(2);
+    deque.push_front(1);
+    deque.push_front(0);
+
+    let rb = RingBuffer::from(deque);
+    assert_eq!(rb.capacity(), 3);
+    assert_eq!(rb.into_vec_deque(), [0, 1, 2]);
+}
+
+#[test]
+fn into_vec_wrapped() {
+    let rb = RingBuffer::<String>::new(4);
+    {
+        let data = unsafe { rb.data.get_mut() };
+        data[3] = MaybeUninit::new("a".to_string());
+        data[4] = MaybeUninit::new("b".to_string());
+        data[0] = MaybeUninit::new("c".to_string());
+    }
+    rb.head.store(3, Ordering::Release);
+    rb.tail.store(1, Ordering::Release);
+
+    let vec = rb.into_vec();
+    assert_eq!(vec, ["a", "b", "c"]);
+    assert!(vec.capacity() >= 5);
+}
+
+#[test]
+fn into_vec_empty() {
+    assert_eq!(RingBuffer::<i32>::new(0).into_vec(), []);
+    assert_eq!(RingBuffer::<i32>::new(3).into_vec(), []);
+}
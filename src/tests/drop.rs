@@ -3,13 +3,13 @@ use std::{cell::RefCell, collections::HashSet};
 use crate::RingBuffer;
 
 #[derive(Debug)]
-struct Dropper<'a> {
-    id: i32,
+pub(super) struct Dropper<'a> {
+    pub(super) id: i32,
     set: &'a RefCell<HashSet<i32>>,
 }
 
 impl<'a> Dropper<'a> {
-    fn new(set: &'a RefCell<HashSet<i32>>, id: i32) -> Self {
+    pub(super) fn new(set: &'a RefCell<HashSet<i32>>, id: i32) -> Self {
         if !set.borrow_mut().insert(id) {
             panic!("value {} already exists", id);
         }
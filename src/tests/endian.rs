@@ -0,0 +1,112 @@
+use std::io;
+
+use crate::RingBuffer;
+
+#[test]
+fn byte_order() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(16).split();
+
+    assert!(prod.push_u32_le(0x1234_5678));
+    assert!(prod.push_u16_be(0xABCD));
+    let mut bytes = [0; 6];
+    assert_eq!(cons.pop_slice(&mut bytes), 6);
+    assert_eq!(bytes, [0x78, 0x56, 0x34, 0x12, 0xAB, 0xCD]);
+
+    assert!(prod.push_u64_be(0x0102_0304_0506_0708));
+    assert_eq!(cons.peek_u64_be(), Some(0x0102_0304_0506_0708));
+    assert_eq!(cons.peek_u64_le(), Some(0x0807_0605_0403_0201));
+    assert_eq!(cons.pop_u64_be(), Some(0x0102_0304_0506_0708));
+
+    assert!(prod.push_i16_le(-2));
+    assert!(prod.push_f32_be(1.5));
+    assert!(prod.push_f64_le(-0.25));
+    assert_eq!(cons.pop_i16_le(), Some(-2));
+    assert_eq!(cons.pop_f32_be(), Some(1.5));
+    assert_eq!(cons.pop_f64_le(), Some(-0.25));
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn partial() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(8).split();
+    for _ in 0..6 {
+        prod.push(0).unwrap();
+        cons.pop().unwrap();
+    }
+
+    assert!(prod.push_u32_be(0xDEAD_BEEF));
+    assert!(!prod.push_u64_le(1));
+    assert_eq!(prod.len(), 4);
+    assert_eq!(cons.pop_u64_le(), None);
+    assert_eq!(cons.len(), 4);
+    // The value straddles the wrap point.
+    assert_eq!(cons.pop_u32_be(), Some(0xDEAD_BEEF));
+}
+
+#[test]
+fn varint() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(16).split();
+    for _ in 0..12 {
+        prod.push(0).unwrap();
+        cons.pop().unwrap();
+    }
+
+    assert!(prod.push_varint(300));
+    assert_eq!(cons.peek_u16_le(), Some(0x02AC));
+    assert_eq!(cons.pop_varint().unwrap(), Some(300));
+
+    assert!(prod.push_varint(u64::MAX));
+    assert_eq!(cons.len(), 10);
+    assert!(!prod.push_varint(1 << 42));
+    assert!(prod.push_varint(0));
+    assert_eq!(cons.pop_varint().unwrap(), Some(u64::MAX));
+    assert_eq!(cons.pop_varint().unwrap(), Some(0));
+    assert_eq!(cons.pop_varint().unwrap(), None);
+
+    prod.push_slice(&[0x80, 0x80]);
+    assert_eq!(cons.pop_varint().unwrap(), None);
+    assert_eq!(cons.len(), 2);
+    prod.push(0x01).unwrap();
+    assert_eq!(cons.pop_varint().unwrap(), Some(1 << 14));
+}
+
+#[test]
+fn varint_malformed() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(16).split();
+
+    // The 10th byte may carry only the highest bit of the value.
+    prod.push_slice(&[0xFF; 9]);
+    prod.push(0x01).unwrap();
+    assert_eq!(cons.pop_varint().unwrap(), Some(u64::MAX));
+
+    prod.push_slice(&[0xFF; 9]);
+    prod.push(0x02).unwrap();
+    let err = cons.pop_varint().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(cons.len(), 10);
+    assert_eq!(cons.discard(10), 10);
+
+    prod.push_slice(&[0x80; 11]);
+    assert_eq!(
+        cons.pop_varint().unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(cons.len(), 11);
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(2).split();
+    prod.push_slice(&[0x80]);
+    prod.resize(4);
+    prod.push_slice(&[0x01]);
+    assert_eq!(cons.len(), 2);
+    assert_eq!(cons.pop_varint().unwrap(), Some(0x80));
+    assert!(cons.is_empty());
+
+    prod.push_slice(&[0x12]);
+    prod.resize(2);
+    prod.push_slice(&[0x34]);
+    assert_eq!(cons.pop_u16_be(), Some(0x1234));
+    assert!(cons.is_empty());
+}
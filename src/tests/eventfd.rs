@@ -0,0 +1,154 @@
+use std::{os::unix::io::AsRawFd, thread};
+
+use crate::{Policy, RingBuffer};
+
+/// Waits for the descriptor to become readable at most `timeout` milliseconds.
+fn readable<F: AsRawFd>(f: &F, timeout: i32) -> bool {
+    let mut pfd = libc::pollfd {
+        fd: f.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let n = unsafe { libc::poll(&mut pfd, 1, timeout) };
+    assert!(n >= 0);
+    n == 1 && pfd.revents & libc::POLLIN != 0
+}
+
+/// Reads the counter of the descriptor and restores it back.
+fn counter<F: AsRawFd>(f: &F) -> u64 {
+    let mut value = 0u64;
+    let n = unsafe {
+        libc::read(
+            f.as_raw_fd(),
+            &mut value as *mut u64 as *mut libc::c_void,
+            8,
+        )
+    };
+    if n == 8 {
+        unsafe {
+            libc::write(
+                f.as_raw_fd(),
+                &value as *const u64 as *const libc::c_void,
+                8,
+            )
+        };
+        value
+    } else {
+        0
+    }
+}
+
+#[test]
+fn readiness() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split_eventfd().unwrap();
+
+    assert!(!readable(&cons, 0));
+    assert!(readable(&prod, 0));
+
+    prod.push(0).unwrap();
+    assert!(readable(&cons, 0));
+    assert!(readable(&prod, 0));
+
+    prod.push(1).unwrap();
+    assert!(readable(&cons, 0));
+    assert!(!readable(&prod, 0));
+
+    assert_eq!(cons.pop(), Some(0));
+    assert!(readable(&cons, 0));
+    assert!(readable(&prod, 0));
+
+    assert_eq!(cons.pop(), Some(1));
+    assert!(!readable(&cons, 0));
+    assert!(readable(&prod, 0));
+}
+
+#[test]
+fn coalesced() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(8).split_eventfd().unwrap();
+
+    for i in 0..6 {
+        prod.push(i).unwrap();
+    }
+    assert_eq!(counter(&cons), 1);
+
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+    assert_eq!(counter(&cons), 1);
+    assert_eq!(counter(&prod), 1);
+
+    assert_eq!(cons.pop_slice(&mut [0; 8]), 4);
+    assert_eq!(counter(&cons), 0);
+    assert_eq!(cons.pop(), None);
+    assert_eq!(counter(&cons), 0);
+}
+
+#[test]
+fn preloaded() {
+    let rb = RingBuffer::from_vec(vec![1, 2], 2);
+    let (prod, cons) = rb.split_eventfd().unwrap();
+    assert!(readable(&cons, 0));
+    assert!(!readable(&prod, 0));
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(1).split_eventfd().unwrap();
+
+    prod.push(0).unwrap();
+    assert!(!readable(&prod, 0));
+    prod.resize(2);
+    prod.push(1).unwrap();
+
+    assert_eq!(cons.pop(), Some(0));
+    assert!(readable(&cons, 0));
+    assert_eq!(cons.pop(), Some(1));
+    assert!(!readable(&cons, 0));
+}
+
+#[test]
+fn threads() {
+    const COUNT: usize = 10_000;
+    let (mut prod, mut cons) = RingBuffer::<usize>::new(7).split_eventfd().unwrap();
+
+    let pjh = thread::spawn(move || {
+        let mut i = 0;
+        while i < COUNT {
+            assert!(readable(&prod, 1000));
+            while i < COUNT && prod.push(i).is_ok() {
+                i += 1;
+            }
+        }
+    });
+
+    let mut next = 0;
+    while next < COUNT {
+        assert!(readable(&cons, 1000));
+        while let Some(x) = cons.pop() {
+            assert_eq!(x, next);
+            next += 1;
+        }
+    }
+
+    pjh.join().unwrap();
+}
+
+#[test]
+fn overwrite() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split_eventfd()
+        .unwrap();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2, 3]), 4);
+    assert!(readable(&cons, 0));
+    assert!(!readable(&prod, 0));
+
+    // Evicting all elements must not leave the descriptor reset while new ones are stored.
+    assert_eq!(prod.push_slice(&[4, 5]), 2);
+    assert!(readable(&cons, 0));
+    assert!(!readable(&prod, 0));
+
+    assert_eq!(cons.pop(), Some(4));
+    assert!(readable(&prod, 0));
+    assert_eq!(cons.pop(), Some(5));
+    assert!(!readable(&cons, 0));
+}
@@ -0,0 +1,84 @@
+use crate::RingBuffer;
+
+#[test]
+fn capacity() {
+    let (mut prod, cons) = RingBuffer::<f32>::split_frames(3, 2);
+    assert_eq!(prod.channels(), 2);
+    assert_eq!(prod.capacity(), 3);
+    assert_eq!(cons.capacity(), 3);
+    assert!(prod.is_empty());
+
+    assert_eq!(prod.push_interleaved(&[0.0; 9]), 3);
+    assert!(prod.is_full());
+    assert!(cons.is_full());
+    assert_eq!(cons.len(), 3);
+    assert_eq!(prod.remaining(), 0);
+    assert_eq!(prod.push_interleaved(&[0.0; 2]), 0);
+}
+
+#[test]
+fn interleaved() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::split_frames(4, 3);
+
+    assert_eq!(prod.push_interleaved(&[0, 1, 2, 3, 4, 5, 6, 7]), 2);
+    assert_eq!(prod.len(), 2);
+
+    let mut out = [-1; 5];
+    assert_eq!(cons.pop_interleaved(&mut out), 1);
+    assert_eq!(out, [0, 1, 2, -1, -1]);
+    assert_eq!(cons.pop_interleaved(&mut out), 1);
+    assert_eq!(out, [3, 4, 5, -1, -1]);
+    assert_eq!(cons.pop_interleaved(&mut out), 0);
+}
+
+#[test]
+fn planar() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::split_frames(4, 2);
+
+    assert_eq!(prod.push_planar(&[&[0, 2, 4], &[1, 3]]), 2);
+
+    let mut tmp = [0; 4];
+    assert_eq!(cons.pop_interleaved(&mut tmp), 2);
+    assert_eq!(tmp, [0, 1, 2, 3]);
+
+    assert_eq!(prod.push_interleaved(&[4, 5, 6, 7, 8, 9]), 3);
+    let (mut left, mut right) = ([0; 4], [0; 2]);
+    assert_eq!(cons.pop_planar(&mut [&mut left, &mut right]), 2);
+    assert_eq!(left, [4, 6, 0, 0]);
+    assert_eq!(right, [5, 7]);
+    assert_eq!(cons.len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn planar_channels() {
+    let (mut prod, _cons) = RingBuffer::<i32>::split_frames(4, 2);
+    prod.push_planar(&[&[0]]);
+}
+
+#[test]
+fn wrap_around() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::split_frames(2, 3);
+
+    let mut wrapped = false;
+    for i in 0..10 {
+        let frame = [3 * i, 3 * i + 1, 3 * i + 2];
+        assert_eq!(prod.push_interleaved(&frame.repeat(2)), 2);
+
+        let n = cons.access_frames(|left, right| {
+            assert_eq!(left.len() % 3, 0);
+            assert_eq!(right.len() % 3, 0);
+            for frame in left.chunks(3).chain(right.chunks(3)) {
+                assert_eq!(frame, [3 * i, 3 * i + 1, 3 * i + 2]);
+            }
+            wrapped |= !right.is_empty();
+            1
+        });
+        assert_eq!(n, 1);
+
+        let mut tmp = [0; 3];
+        assert_eq!(cons.pop_interleaved(&mut tmp), 1);
+        assert_eq!(tmp, frame);
+    }
+    assert!(wrapped);
+}
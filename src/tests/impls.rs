@@ -0,0 +1,73 @@
+use std::{mem::MaybeUninit, sync::atomic::Ordering};
+
+use crate::RingBuffer;
+
+/// Creates a ring buffer with elements wrapping around the end of its memory.
+fn wrapped(cap: usize, elems: &[i32]) -> RingBuffer<i32> {
+    let rb = RingBuffer::new(cap);
+    let data = unsafe { rb.data.get_mut() };
+    let len = data.len();
+    let head = len - 1;
+    for (i, e) in elems.iter().enumerate() {
+        data[(head + i) % len] = MaybeUninit::new(*e);
+    }
+    rb.head.store(head, Ordering::Release);
+    rb.tail.store((head + elems.len()) % len, Ordering::Release);
+    rb
+}
+
+#[test]
+fn debug() {
+    let rb = wrapped(3, &[1, 2, 3]);
+    assert_eq!(
+        format!("{:?}", rb),
+        "RingBuffer { capacity: 3, len: 3, elements: [1, 2, 3] }"
+    );
+
+    let (mut prod, mut cons) = rb.split();
+    assert_eq!(cons.pop(), Some(1));
+    assert_eq!(format!("{:?}", prod), "Producer { capacity: 3, len: 2 }");
+    assert_eq!(
+        format!("{:?}", cons),
+        "Consumer { capacity: 3, len: 2, elements: [2, 3] }"
+    );
+
+    prod.push(4).unwrap();
+    assert_eq!(
+        format!("{:?}", cons),
+        "Consumer { capacity: 3, len: 3, elements: [2, 3, 4] }"
+    );
+}
+
+#[test]
+fn clone() {
+    let rb = wrapped(4, &[1, 2, 3]);
+    let copy = rb.clone();
+    assert_eq!(copy.capacity(), 4);
+    assert_eq!(copy, rb);
+
+    let (_, mut cons) = copy.split();
+    assert_eq!(cons.pop(), Some(1));
+    assert_eq!(cons.pop(), Some(2));
+    assert_eq!(cons.pop(), Some(3));
+    assert_eq!(cons.pop(), None);
+}
+
+#[test]
+fn clone_vec() {
+    let rb = RingBuffer::new(2).preload(&mut vec![vec![1, 2], vec![3]].into_iter());
+    let copy = rb.clone();
+    drop(rb);
+    assert_eq!(
+        format!("{:?}", copy),
+        "RingBuffer { capacity: 2, len: 2, elements: [[1, 2], [3]] }"
+    );
+}
+
+#[test]
+fn eq() {
+    assert_eq!(wrapped(3, &[1, 2]), wrapped(5, &[1, 2]));
+    assert_eq!(wrapped(3, &[]), RingBuffer::new(1));
+    assert_ne!(wrapped(3, &[1, 2]), wrapped(3, &[1, 2, 3]));
+    assert_ne!(wrapped(3, &[1, 2]), wrapped(3, &[2, 1]));
+}
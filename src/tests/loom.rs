@@ -0,0 +1,221 @@
+//! Model tests checking all interleavings of producer and consumer.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --release --lib tests::loom`.
+
+use loom::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use crate::{move_items, RingBuffer};
+
+/// Pushes all `elems` spinning while the buffer is full.
+fn push_all<T>(prod: &mut crate::Producer<T>, elems: impl IntoIterator<Item = T>) {
+    for mut elem in elems {
+        while let Err(e) = prod.push(elem) {
+            elem = e;
+            thread::yield_now();
+        }
+    }
+}
+
+/// Pops `count` elements spinning while the buffer is empty.
+fn pop_all<T>(cons: &mut crate::Consumer<T>, count: usize) -> Vec<T> {
+    let mut elems = Vec::new();
+    while elems.len() < count {
+        match cons.pop() {
+            Some(elem) => elems.push(elem),
+            None => thread::yield_now(),
+        }
+    }
+    elems
+}
+
+#[test]
+fn push_pop() {
+    loom::model(|| {
+        let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+
+        let pjh = thread::spawn(move || push_all(&mut prod, 0..3));
+
+        assert_eq!(pop_all(&mut cons, 3), [0, 1, 2]);
+        assert!(cons.is_empty());
+
+        pjh.join().unwrap();
+    });
+}
+
+#[test]
+fn wrap_around() {
+    loom::model(|| {
+        let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+        prod.push(0).unwrap();
+        assert_eq!(cons.pop(), Some(0));
+
+        let pjh = thread::spawn(move || {
+            assert_eq!(prod.push_slice(&[1, 2]), 2);
+            prod
+        });
+
+        let mut tmp = [0; 2];
+        let mut n = 0;
+        while n < 2 {
+            n += cons.pop_slice(&mut tmp[n..]);
+            if n < 2 {
+                thread::yield_now();
+            }
+        }
+        assert_eq!(tmp, [1, 2]);
+
+        let prod = pjh.join().unwrap();
+        assert!(prod.is_empty());
+        assert_eq!(prod.remaining(), 2);
+    });
+}
+
+#[test]
+fn move_between() {
+    loom::model(|| {
+        let (mut prod_a, mut cons_a) = RingBuffer::<i32>::new(1).split();
+        let (mut prod_b, mut cons_b) = RingBuffer::<i32>::new(1).split();
+
+        let pjh = thread::spawn(move || push_all(&mut prod_a, 0..2));
+        let mjh = thread::spawn(move || {
+            let mut n = 0;
+            while n < 2 {
+                n += move_items(&mut cons_a, &mut prod_b, None);
+                if n < 2 {
+                    thread::yield_now();
+                }
+            }
+        });
+
+        assert_eq!(pop_all(&mut cons_b, 2), [0, 1]);
+
+        pjh.join().unwrap();
+        mjh.join().unwrap();
+    });
+}
+
+/// Increments the shared counter when dropped.
+struct Counted(Arc<AtomicUsize>);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn drop_pending() {
+    loom::model(|| {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let (mut prod, mut cons) = RingBuffer::<Counted>::new(2).split();
+
+        let counter = dropped.clone();
+        let pjh = thread::spawn(move || {
+            push_all(&mut prod, (0..2).map(|_| Counted(counter.clone())));
+        });
+
+        // Storage is freed by the thread which drops its part last.
+        let popped = cons.pop();
+        drop(cons);
+        pjh.join().unwrap();
+        drop(popped);
+
+        assert_eq!(dropped.load(Ordering::Relaxed), 2);
+    });
+}
+
+#[test]
+fn triple_buffer() {
+    loom::model(|| {
+        let (mut prod, mut cons) = crate::TripleBuffer::new(0).split();
+
+        let pjh = thread::spawn(move || {
+            prod.write(1);
+            prod.write(2);
+        });
+
+        let first = *cons.read();
+        let second = *cons.read();
+        assert!(first <= second);
+
+        pjh.join().unwrap();
+        assert_eq!(*cons.read(), 2);
+    });
+}
+
+#[test]
+fn watermarks() {
+    loom::model(|| {
+        let (mut prod, mut cons) = RingBuffer::<i32>::new(2).with_watermarks(1, 1).split();
+
+        let pjh = thread::spawn(move || {
+            push_all(&mut prod, 0..2);
+            prod
+        });
+
+        assert_eq!(pop_all(&mut cons, 2), [0, 1]);
+
+        // The low watermark must not be missed whatever order the flag is updated in.
+        let prod = pjh.join().unwrap();
+        assert!(!prod.above_watermark());
+    });
+}
+
+#[test]
+fn overwrite_oldest() {
+    loom::model(|| {
+        let (mut prod, mut cons) = RingBuffer::<i32>::new(1)
+            .with_policy(crate::Policy::OverwriteOldest)
+            .split();
+        prod.push(0).unwrap();
+
+        let pjh = thread::spawn(move || {
+            prod.push(1).unwrap();
+            prod
+        });
+
+        let first = cons.pop();
+        let prod = pjh.join().unwrap();
+        match first {
+            Some(0) => assert_eq!(cons.pop(), Some(1)),
+            Some(1) => assert_eq!(prod.discarded(), 1),
+            // The oldest element has been dropped but the new one hasn't been pushed yet.
+            None => {
+                assert_eq!(prod.discarded(), 1);
+                assert_eq!(cons.pop(), Some(1));
+            }
+            _ => panic!("unexpected {:?}", first),
+        }
+        assert!(cons.is_empty());
+    });
+}
+
+#[test]
+fn read_until_delim() {
+    loom::model(|| {
+        let (mut prod, mut cons) = RingBuffer::<u8>::new(2)
+            .with_policy(crate::Policy::OverwriteOldest)
+            .split();
+        prod.push_slice(b"a;");
+
+        let pjh = thread::spawn(move || {
+            prod.push(b'b').unwrap();
+        });
+
+        // The bytes must not be overwritten between the search and the removal.
+        let mut line = Vec::new();
+        match cons.read_until_delim(b";", &mut line) {
+            Some(2) => assert_eq!(line, b"a;"),
+            Some(1) => assert_eq!(line, b";"),
+            n => panic!("unexpected {:?}", n),
+        }
+        pjh.join().unwrap();
+        assert_eq!(cons.pop(), Some(b'b'));
+    });
+}
@@ -1,6 +1,37 @@
 mod access;
+mod bounds;
+mod builder;
+mod convert;
 mod drop;
+mod endian;
+#[cfg(feature = "eventfd")]
+mod eventfd;
+mod frames;
+mod impls;
+#[cfg(loom)]
+mod loom;
 mod message;
+mod model;
+mod move_map;
 mod multiple;
+mod panic;
+mod pod;
+mod policy;
+mod predicate;
 mod read_write;
+mod resize;
+mod search;
+#[cfg(feature = "select")]
+mod select;
+#[cfg(feature = "seq")]
+mod sequence;
+#[cfg(feature = "serde")]
+mod serialize;
 mod single;
+#[cfg(feature = "stats")]
+mod stats;
+mod timed;
+mod traits;
+mod transaction;
+mod triple;
+mod watermark;
@@ -0,0 +1,462 @@
+use std::{
+    cell::RefCell,
+    cmp::min,
+    collections::{HashSet, VecDeque},
+    io::{self, Read, Write},
+};
+
+use super::drop::Dropper;
+use crate::{Consumer, Producer, RingBuffer};
+
+const SEEDS: u64 = 256;
+const STEPS: usize = 256;
+
+/// Simple seeded generator (xorshift64*), so that failures are reproducible without extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Random number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Random count, `None` in a quarter of cases.
+    fn count(&mut self, max: usize) -> Option<usize> {
+        if self.below(4) == 0 {
+            None
+        } else {
+            Some(self.below(max + 1))
+        }
+    }
+}
+
+/// Reference model of the ring buffer.
+///
+/// Besides the contents it tracks the head position in the storage
+/// to know the size of contiguous regions used by `read_from` and `write_into`.
+struct Model<T> {
+    deque: VecDeque<T>,
+    capacity: usize,
+    head: usize,
+}
+
+impl<T> Model<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            deque: VecDeque::new(),
+            capacity,
+            head: 0,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.capacity - self.deque.len()
+    }
+
+    fn push(&mut self, elem: T) -> Result<(), T> {
+        if self.remaining() > 0 {
+            self.deque.push_back(elem);
+            Ok(())
+        } else {
+            Err(elem)
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let elem = self.deque.pop_front();
+        if elem.is_some() {
+            self.head = (self.head + 1) % (self.capacity + 1);
+        }
+        elem
+    }
+
+    fn tail(&self) -> usize {
+        (self.head + self.deque.len()) % (self.capacity + 1)
+    }
+
+    /// Size of the first vacant slice given to `push_access`.
+    fn vacant_left(&self) -> usize {
+        let (head, tail, size) = (self.head, self.tail(), self.capacity + 1);
+        if tail >= head {
+            if head > 0 {
+                size - tail
+            } else {
+                size - 1 - tail
+            }
+        } else {
+            head - 1 - tail
+        }
+    }
+
+    /// Size of the first occupied slice given to `pop_access`.
+    fn occupied_left(&self) -> usize {
+        let (head, tail, size) = (self.head, self.tail(), self.capacity + 1);
+        if head <= tail {
+            tail - head
+        } else {
+            size - head
+        }
+    }
+}
+
+/// Checks everything observable through both halves against the model.
+fn check<T: Clone + PartialEq + std::fmt::Debug>(
+    prod: &Producer<T>,
+    cons: &Consumer<T>,
+    model: &Model<T>,
+    ctx: &str,
+) {
+    let len = model.deque.len();
+    for (capacity, l, empty, full, remaining) in [
+        (
+            prod.capacity(),
+            prod.len(),
+            prod.is_empty(),
+            prod.is_full(),
+            prod.remaining(),
+        ),
+        (
+            cons.capacity(),
+            cons.len(),
+            cons.is_empty(),
+            cons.is_full(),
+            cons.remaining(),
+        ),
+    ] {
+        assert_eq!(capacity, model.capacity, "{}", ctx);
+        assert_eq!(l, len, "{}", ctx);
+        assert_eq!(empty, len == 0, "{}", ctx);
+        assert_eq!(full, len == model.capacity, "{}", ctx);
+        assert_eq!(remaining, model.remaining(), "{}", ctx);
+    }
+
+    assert!(cons.iter().eq(model.deque.iter()), "{}", ctx);
+    let (left, right) = cons.as_slices();
+    assert!(left.iter().chain(right).eq(model.deque.iter()), "{}", ctx);
+    let mut visited = Vec::new();
+    cons.for_each(|x| visited.push(x.clone()));
+    assert!(visited.iter().eq(model.deque.iter()), "{}", ctx);
+}
+
+/// Reader returning at most `chunk` bytes at once.
+struct ChunkedReader {
+    next: u8,
+    chunk: usize,
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let n = min(buffer.len(), self.chunk);
+        for b in &mut buffer[..n] {
+            *b = self.next;
+            self.next = self.next.wrapping_add(1);
+        }
+        Ok(n)
+    }
+}
+
+/// Writer accepting at most `chunk` bytes at once.
+struct ChunkedWriter {
+    data: Vec<u8>,
+    chunk: usize,
+}
+
+impl Write for ChunkedWriter {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        let n = min(buffer.len(), self.chunk);
+        self.data.extend_from_slice(&buffer[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn run_bytes(seed: u64) {
+    let mut rng = Rng::new(seed);
+    let capacity = 1 + rng.below(8);
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(capacity).split();
+    let mut model = Model::new(capacity);
+    let mut next = 0u8;
+
+    for step in 0..STEPS {
+        let op = rng.below(13);
+        let ctx = format!("seed {}, step {}, op {}", seed, step, op);
+        match op {
+            0 => {
+                assert_eq!(prod.push(next), model.push(next), "{}", ctx);
+                next = next.wrapping_add(1);
+            }
+            1 => assert_eq!(cons.pop(), model.pop(), "{}", ctx),
+            2 => {
+                let elems = (0..rng.below(capacity + 3))
+                    .map(|i| next.wrapping_add(i as u8))
+                    .collect::<Vec<_>>();
+                let n = prod.push_slice(&elems);
+                assert_eq!(n, min(elems.len(), model.remaining()), "{}", ctx);
+                for &x in &elems[..n] {
+                    model.push(x).unwrap();
+                }
+                next = next.wrapping_add(n as u8);
+            }
+            3 => {
+                let mut elems = vec![0; rng.below(capacity + 3)];
+                let n = cons.pop_slice(&mut elems);
+                assert_eq!(n, min(elems.len(), model.deque.len()), "{}", ctx);
+                for &x in &elems[..n] {
+                    assert_eq!(Some(x), model.pop(), "{}", ctx);
+                }
+            }
+            4 => {
+                let count = rng.count(capacity + 1);
+                let stop = 1 + rng.below(capacity + 1);
+                let mut popped = Vec::new();
+                let n = cons.pop_each(
+                    |x| {
+                        popped.push(x);
+                        popped.len() < stop
+                    },
+                    count,
+                );
+                let limit = min(count.unwrap_or(capacity), model.deque.len());
+                assert_eq!(n, min(limit, stop), "{}", ctx);
+                assert_eq!(popped.len(), n, "{}", ctx);
+                for x in popped {
+                    assert_eq!(Some(x), model.pop(), "{}", ctx);
+                }
+            }
+            5 => {
+                let count = rng.below(capacity + 2);
+                let n = cons.discard(count);
+                assert_eq!(n, min(count, model.deque.len()), "{}", ctx);
+                for _ in 0..n {
+                    model.pop().unwrap();
+                }
+            }
+            6 => {
+                let count = rng.count(capacity + 1);
+                let n = cons.move_to(&mut prod, count);
+                let limit = min(model.deque.len(), model.remaining());
+                assert_eq!(n, min(count.unwrap_or(limit), limit), "{}", ctx);
+                for _ in 0..n {
+                    let x = model.pop().unwrap();
+                    model.push(x).unwrap();
+                }
+            }
+            7 => {
+                let count = rng.count(capacity + 1);
+                let chunk = rng.below(capacity + 2);
+                let mut reader = ChunkedReader { next, chunk };
+                let n = prod.read_from(&mut reader, count).unwrap();
+                let limit = min(count.unwrap_or(capacity), model.vacant_left());
+                assert_eq!(n, min(limit, chunk), "{}", ctx);
+                for i in 0..n {
+                    model.push(next.wrapping_add(i as u8)).unwrap();
+                }
+                next = reader.next;
+            }
+            8 => {
+                let count = rng.count(capacity + 1);
+                let chunk = rng.below(capacity + 2);
+                let mut writer = ChunkedWriter {
+                    data: Vec::new(),
+                    chunk,
+                };
+                let n = cons.write_into(&mut writer, count).unwrap();
+                let limit = min(count.unwrap_or(capacity), model.occupied_left());
+                assert_eq!(n, min(limit, chunk), "{}", ctx);
+                assert_eq!(writer.data.len(), n, "{}", ctx);
+                for x in writer.data {
+                    assert_eq!(Some(x), model.pop(), "{}", ctx);
+                }
+            }
+            9 => {
+                let delta = rng.below(256) as u8;
+                cons.access_mut(|left, right| {
+                    for x in left.iter_mut().chain(right.iter_mut()) {
+                        *x = x.wrapping_add(delta);
+                    }
+                });
+                for x in model.deque.iter_mut() {
+                    *x = x.wrapping_add(delta);
+                }
+            }
+            10 => {
+                let mut elems = (0..rng.below(capacity + 3)).map(|i| next.wrapping_add(i as u8));
+                let n = prod.push_iter(&mut elems);
+                assert_eq!(n, min(elems.len() + n, model.remaining()), "{}", ctx);
+                for i in 0..n {
+                    model.push(next.wrapping_add(i as u8)).unwrap();
+                }
+                next = next.wrapping_add(n as u8);
+            }
+            11 => {
+                let elems = (0..rng.below(capacity + 3))
+                    .map(|i| next.wrapping_add(i as u8))
+                    .collect::<Vec<_>>();
+                match prod.write(&elems) {
+                    Ok(n) => {
+                        assert_eq!(n, min(elems.len(), model.remaining()), "{}", ctx);
+                        for &x in &elems[..n] {
+                            model.push(x).unwrap();
+                        }
+                        next = next.wrapping_add(n as u8);
+                    }
+                    Err(e) => {
+                        assert_eq!(e.kind(), io::ErrorKind::WouldBlock, "{}", ctx);
+                        assert!(!elems.is_empty() && model.remaining() == 0, "{}", ctx);
+                    }
+                }
+            }
+            12 => {
+                let mut elems = vec![0; rng.below(capacity + 3)];
+                match cons.read(&mut elems) {
+                    Ok(n) => {
+                        assert_eq!(n, min(elems.len(), model.deque.len()), "{}", ctx);
+                        for &x in &elems[..n] {
+                            assert_eq!(Some(x), model.pop(), "{}", ctx);
+                        }
+                    }
+                    Err(e) => {
+                        assert_eq!(e.kind(), io::ErrorKind::WouldBlock, "{}", ctx);
+                        assert!(!elems.is_empty() && model.deque.is_empty(), "{}", ctx);
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+        check(&prod, &cons, &model, &ctx);
+    }
+}
+
+fn run_droppers(seed: u64) {
+    let set = RefCell::new(HashSet::new());
+    {
+        let mut rng = Rng::new(seed);
+        let capacity = 1 + rng.below(8);
+        let (mut prod, mut cons) = RingBuffer::<Dropper>::new(capacity).split();
+        let mut model = Model::<i32>::new(capacity);
+        let mut next = 0;
+
+        for step in 0..STEPS {
+            let op = rng.below(8);
+            let ctx = format!("seed {}, step {}, op {}", seed, step, op);
+            match op {
+                0 => {
+                    let res = prod.push(Dropper::new(&set, next));
+                    assert_eq!(res.map_err(|d| d.id), model.push(next), "{}", ctx);
+                    next += 1;
+                }
+                1 => assert_eq!(cons.pop().map(|d| d.id), model.pop(), "{}", ctx),
+                2 => {
+                    let count = rng.below(capacity + 3);
+                    let mut i = 0;
+                    let n = prod.push_each(|| {
+                        if i < count {
+                            i += 1;
+                            next += 1;
+                            Some(Dropper::new(&set, next - 1))
+                        } else {
+                            None
+                        }
+                    });
+                    assert_eq!(n, min(count, model.remaining()), "{}", ctx);
+                    for id in (next - n as i32)..next {
+                        model.push(id).unwrap();
+                    }
+                }
+                3 => {
+                    let mut elems = (0..rng.below(capacity + 3)).map(|i| next + i as i32);
+                    let n = prod.push_iter(&mut (&mut elems).map(|id| Dropper::new(&set, id)));
+                    assert_eq!(n, min(n + elems.len(), model.remaining()), "{}", ctx);
+                    for id in next..(next + n as i32) {
+                        model.push(id).unwrap();
+                    }
+                    next += n as i32;
+                }
+                4 => {
+                    let count = rng.count(capacity + 1);
+                    let stop = 1 + rng.below(capacity + 1);
+                    let mut popped = Vec::new();
+                    let n = cons.pop_each(
+                        |d| {
+                            popped.push(d.id);
+                            popped.len() < stop
+                        },
+                        count,
+                    );
+                    let limit = min(count.unwrap_or(capacity), model.deque.len());
+                    assert_eq!(n, min(limit, stop), "{}", ctx);
+                    assert_eq!(popped.len(), n, "{}", ctx);
+                    for id in popped {
+                        assert_eq!(Some(id), model.pop(), "{}", ctx);
+                    }
+                }
+                5 => {
+                    let count = rng.below(capacity + 2);
+                    let n = cons.discard(count);
+                    assert_eq!(n, min(count, model.deque.len()), "{}", ctx);
+                    for _ in 0..n {
+                        model.pop().unwrap();
+                    }
+                }
+                6 => {
+                    let count = rng.count(capacity + 1);
+                    let n = cons.move_to(&mut prod, count);
+                    let limit = min(model.deque.len(), model.remaining());
+                    assert_eq!(n, min(count.unwrap_or(limit), limit), "{}", ctx);
+                    for _ in 0..n {
+                        let id = model.pop().unwrap();
+                        model.push(id).unwrap();
+                    }
+                }
+                7 => {
+                    let mut ids = Vec::new();
+                    cons.access_mut(|left, right| {
+                        ids.extend(left.iter().chain(right.iter()).map(|d| d.id));
+                    });
+                    assert!(ids.iter().eq(model.deque.iter()), "{}", ctx);
+                }
+                _ => unreachable!(),
+            }
+
+            assert!(
+                cons.iter().map(|d| d.id).eq(model.deque.iter().cloned()),
+                "{}",
+                ctx
+            );
+            assert_eq!(cons.len(), model.deque.len(), "{}", ctx);
+            assert_eq!(prod.remaining(), model.remaining(), "{}", ctx);
+            let live = set.borrow();
+            assert_eq!(live.len(), model.deque.len(), "{}", ctx);
+            assert!(model.deque.iter().all(|id| live.contains(id)), "{}", ctx);
+        }
+    }
+    assert!(set.borrow().is_empty(), "seed {}", seed);
+}
+
+#[test]
+fn bytes() {
+    for seed in 0..SEEDS {
+        run_bytes(seed);
+    }
+}
+
+#[test]
+fn droppers() {
+    for seed in 0..SEEDS {
+        run_droppers(seed);
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    mem::MaybeUninit,
+    num::NonZeroU16,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use super::drop::Dropper;
+use crate::{move_map, move_map_slices, RingBuffer};
+
+#[test]
+fn convert() {
+    let (mut src_prod, mut src_cons) = RingBuffer::<i16>::new(4).split();
+    let (mut dst_prod, mut dst_cons) = RingBuffer::<f32>::new(3).split();
+
+    // Make both buffers wrap around.
+    assert_eq!(src_prod.push_slice(&[0, 0, 0]), 3);
+    assert_eq!(src_cons.discard(3), 3);
+    assert_eq!(dst_prod.push_slice(&[0.0; 2]), 2);
+    assert_eq!(dst_cons.discard(2), 2);
+
+    assert_eq!(src_prod.push_slice(&[1, 2, 3, 4]), 4);
+    assert_eq!(
+        move_map(&mut src_cons, &mut dst_prod, None, |x| x as f32 / 2.0),
+        3
+    );
+    assert_eq!(src_cons.iter().cloned().collect::<Vec<_>>(), [4]);
+    assert_eq!(
+        dst_cons.iter().cloned().collect::<Vec<_>>(),
+        [0.5, 1.0, 1.5]
+    );
+
+    assert_eq!(dst_cons.discard(3), 3);
+    assert_eq!(
+        move_map(&mut src_cons, &mut dst_prod, Some(0), |x| x as f32),
+        0
+    );
+    assert_eq!(
+        move_map(&mut src_cons, &mut dst_prod, Some(5), |x| x as f32),
+        1
+    );
+    assert_eq!(dst_cons.pop(), Some(4.0));
+}
+
+#[test]
+fn parse() {
+    let (mut src_prod, mut src_cons) = RingBuffer::<&str>::new(4).split();
+    let (mut dst_prod, mut dst_cons) = RingBuffer::<Result<i32, String>>::new(4).split();
+
+    src_prod.push_iter(&mut ["1", "x", "3"].iter().cloned());
+    assert_eq!(
+        move_map(&mut src_cons, &mut dst_prod, Some(2), |s| s
+            .parse()
+            .map_err(|_| s.to_string())),
+        2
+    );
+    assert_eq!(dst_cons.pop(), Some(Ok(1)));
+    assert_eq!(dst_cons.pop(), Some(Err("x".to_string())));
+    assert_eq!(src_cons.len(), 1);
+}
+
+#[test]
+fn panic() {
+    let set = RefCell::new(HashSet::new());
+    {
+        let (mut src_prod, mut src_cons) = RingBuffer::new(4).split();
+        let (mut dst_prod, dst_cons) = RingBuffer::new(4).split();
+        for id in 0..4 {
+            src_prod.push(Dropper::new(&set, id)).unwrap();
+        }
+
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            move_map(&mut src_cons, &mut dst_prod, None, |d: Dropper| {
+                if d.id == 2 {
+                    panic!("test");
+                }
+                d.id * 10
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(dst_cons.iter().cloned().collect::<Vec<_>>(), [0, 10]);
+        assert_eq!(src_cons.iter().map(|d| d.id).collect::<Vec<_>>(), [3]);
+        assert_eq!(set.borrow().len(), 1);
+    }
+    assert!(set.borrow().is_empty());
+}
+
+#[test]
+fn slices() {
+    let (mut src_prod, mut src_cons) = RingBuffer::<i16>::new(5).split();
+    let (mut dst_prod, mut dst_cons) = RingBuffer::<f32>::new(4).split();
+
+    assert_eq!(src_prod.push_slice(&[0; 4]), 4);
+    assert_eq!(src_cons.discard(4), 4);
+    assert_eq!(dst_prod.push_slice(&[0.0; 2]), 2);
+    assert_eq!(dst_cons.discard(2), 2);
+
+    assert_eq!(src_prod.push_slice(&[1, 2, 3, 4, 5]), 5);
+    let mut calls = 0;
+    assert_eq!(
+        unsafe {
+            move_map_slices(&mut src_cons, &mut dst_prod, None, |src, dst| {
+                assert_eq!(src.len(), dst.len());
+                for (d, s) in dst.iter_mut().zip(src) {
+                    *d = MaybeUninit::new(f32::from(*s));
+                }
+                calls += 1;
+            })
+        },
+        4
+    );
+    assert_eq!(calls, 3);
+    assert_eq!(
+        dst_cons.iter().cloned().collect::<Vec<_>>(),
+        [1.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(src_cons.pop(), Some(5));
+}
+
+#[test]
+fn slices_no_default() {
+    let (mut src_prod, mut src_cons) = RingBuffer::<u8>::new(3).split();
+    let (mut dst_prod, mut dst_cons) = RingBuffer::<NonZeroU16>::new(3).split();
+
+    assert_eq!(src_prod.push_slice(&[1, 2]), 2);
+    assert_eq!(
+        unsafe {
+            move_map_slices(&mut src_cons, &mut dst_prod, None, |src, dst| {
+                for (d, s) in dst.iter_mut().zip(src) {
+                    *d = MaybeUninit::new(NonZeroU16::new(u16::from(*s) * 100).unwrap());
+                }
+            })
+        },
+        2
+    );
+    assert_eq!(dst_cons.pop().map(NonZeroU16::get), Some(100));
+    assert_eq!(dst_cons.pop().map(NonZeroU16::get), Some(200));
+}
+
+#[test]
+fn resized() {
+    let (mut src_prod, mut src_cons) = RingBuffer::<i32>::new(2).split();
+    let (mut dst_prod, dst_cons) = RingBuffer::<i64>::new(4).split();
+
+    assert_eq!(src_prod.push_slice(&[1, 2]), 2);
+    src_prod.resize(2);
+    assert_eq!(src_prod.push_slice(&[3]), 1);
+    assert_eq!(move_map(&mut src_cons, &mut dst_prod, None, i64::from), 3);
+    assert_eq!(dst_cons.iter().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+    assert!(src_cons.is_empty());
+}
@@ -0,0 +1,122 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use super::drop::Dropper;
+use crate::RingBuffer;
+
+#[test]
+fn push_each() {
+    let set = RefCell::new(HashSet::new());
+    {
+        let (mut prod, mut cons) = RingBuffer::new(4).split();
+
+        // Make the vacant space wrap around.
+        for id in 0..3 {
+            prod.push(Dropper::new(&set, id)).unwrap();
+            assert_eq!(cons.pop().unwrap().id, id);
+        }
+
+        let mut id = 3;
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            prod.push_each(|| {
+                if id == 6 {
+                    panic!("test");
+                }
+                id += 1;
+                Some(Dropper::new(&set, id - 1))
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(set.borrow().len(), 3);
+        assert_eq!(prod.len(), 3);
+        assert_eq!(cons.iter().map(|d| d.id).collect::<Vec<_>>(), [3, 4, 5]);
+
+        assert_eq!(cons.pop().unwrap().id, 3);
+        assert_eq!(set.borrow().len(), 2);
+    }
+    assert!(set.borrow().is_empty());
+}
+
+#[test]
+fn pop_each() {
+    let set = RefCell::new(HashSet::new());
+    {
+        let (mut prod, mut cons) = RingBuffer::new(4).split();
+
+        prod.push(Dropper::new(&set, 0)).unwrap();
+        assert_eq!(cons.pop().unwrap().id, 0);
+        for id in 1..5 {
+            prod.push(Dropper::new(&set, id)).unwrap();
+        }
+
+        let mut ids = Vec::new();
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            cons.pop_each(
+                |d| {
+                    ids.push(d.id);
+                    if d.id == 3 {
+                        panic!("test");
+                    }
+                    true
+                },
+                None,
+            )
+        }));
+        assert!(res.is_err());
+        assert_eq!(ids, [1, 2, 3]);
+        assert_eq!(set.borrow().len(), 1);
+        assert_eq!(cons.len(), 1);
+        assert_eq!(prod.remaining(), 3);
+        assert_eq!(cons.iter().map(|d| d.id).collect::<Vec<_>>(), [4]);
+    }
+    assert!(set.borrow().is_empty());
+}
+
+#[test]
+fn for_each_mut() {
+    let set = RefCell::new(HashSet::new());
+    {
+        let (mut prod, mut cons) = RingBuffer::new(3).split();
+        for id in 0..3 {
+            prod.push(Dropper::new(&set, id)).unwrap();
+        }
+
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            cons.for_each_mut(|d| {
+                if d.id == 1 {
+                    panic!("test");
+                }
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(set.borrow().len(), 3);
+        assert_eq!(cons.len(), 3);
+    }
+    assert!(set.borrow().is_empty());
+}
+
+#[test]
+fn access_mut() {
+    let set = RefCell::new(HashSet::new());
+    {
+        let (mut prod, mut cons) = RingBuffer::new(3).split();
+        for id in 0..3 {
+            prod.push(Dropper::new(&set, id)).unwrap();
+        }
+
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            cons.access_mut(|left, right| {
+                left.swap(0, 2);
+                assert!(right.is_empty());
+                panic!("test");
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(set.borrow().len(), 3);
+        assert_eq!(cons.iter().map(|d| d.id).collect::<Vec<_>>(), [2, 1, 0]);
+    }
+    assert!(set.borrow().is_empty());
+}
@@ -0,0 +1,105 @@
+use std::{thread, time::Duration};
+
+use crate::{Pod, Policy, RingBuffer};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Header {
+    id: u32,
+    len: u16,
+    flags: [u8; 2],
+}
+
+unsafe impl Pod for Header {}
+
+const HEADER: Header = Header {
+    id: 0x1234_5678,
+    len: 42,
+    flags: [1, 2],
+};
+
+#[test]
+fn push_pop() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(16).split();
+    assert_eq!(cons.pop_pod::<Header>(), None);
+
+    assert!(prod.push_pod(&HEADER));
+    assert!(prod.push_pod(&0xABu8));
+    assert_eq!(prod.len(), 9);
+    assert_eq!(cons.peek_pod::<u32>(), Some(HEADER.id));
+    assert_eq!(cons.pop_pod::<Header>(), Some(HEADER));
+    assert_eq!(cons.pop_pod::<u16>(), None);
+    assert_eq!(cons.pop_pod::<u8>(), Some(0xAB));
+}
+
+#[test]
+fn wrap() {
+    for offset in 0..17 {
+        let (mut prod, mut cons) = RingBuffer::<u8>::new(16).split();
+        for _ in 0..offset {
+            prod.push(0).unwrap();
+            cons.pop().unwrap();
+        }
+        assert!(prod.push_pod(&HEADER));
+        assert!(prod.push_pod(&[1.5f32, -2.0]));
+        assert!(!prod.push_pod(&0u64));
+
+        assert_eq!(cons.peek_pod::<Header>(), Some(HEADER));
+        assert_eq!(cons.pop_pod::<Header>(), Some(HEADER));
+        assert_eq!(cons.pop_pod::<[f32; 2]>(), Some([1.5, -2.0]));
+        assert!(cons.is_empty());
+    }
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(2).split();
+    prod.push_slice(&[1, 2]);
+    prod.resize(4);
+    prod.push_slice(&[3, 4]);
+    assert_eq!(cons.len(), 4);
+
+    assert_eq!(cons.peek_pod::<u32>(), Some(u32::from_ne_bytes([1, 2, 3, 4])));
+    assert_eq!(cons.pop_pod::<u32>(), Some(u32::from_ne_bytes([1, 2, 3, 4])));
+    assert!(cons.is_empty());
+    assert_eq!(cons.pop_pod::<u8>(), None);
+}
+
+#[test]
+fn too_large() {
+    for &policy in [
+        Policy::Reject,
+        Policy::DropNewest,
+        Policy::OverwriteOldest,
+        Policy::Block(None),
+    ]
+    .iter()
+    {
+        let (mut prod, cons) = RingBuffer::<u8>::new(4).with_policy(policy).split();
+        assert!(prod.push_pod(&0u16));
+        assert!(!prod.push_pod(&HEADER));
+        assert_eq!(prod.discarded(), 0);
+        assert_eq!(cons.len(), 2);
+    }
+}
+
+#[test]
+fn block() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(4)
+        .with_policy(Policy::Block(None))
+        .split();
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+
+    // The producer must wait until there is space for the whole value, not for a single byte.
+    let pjh = thread::spawn(move || {
+        assert!(prod.push_pod(&[3u8, 4, 5]));
+        prod
+    });
+    for i in 0..3 {
+        thread::sleep(Duration::from_millis(1));
+        assert_eq!(cons.pop(), Some(i));
+    }
+    let prod = pjh.join().unwrap();
+    assert_eq!(prod.len(), 3);
+    assert_eq!(cons.pop_pod::<[u8; 3]>(), Some([3, 4, 5]));
+}
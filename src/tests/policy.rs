@@ -0,0 +1,243 @@
+use std::{
+    io::{Read, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::{Policy, RingBuffer};
+
+#[test]
+fn reject() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+    assert_eq!(prod.policy(), Policy::Reject);
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 2);
+    assert_eq!(prod.push(3), Err(3));
+    let mut iter = 4..6;
+    assert_eq!(prod.push_iter(&mut iter), 0);
+    assert_eq!(iter, 4..6);
+    assert_eq!(prod.discarded(), 0);
+
+    assert_eq!(cons.pop_slice(&mut [0; 3]), 2);
+}
+
+#[test]
+fn drop_newest() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::DropNewest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(prod.push(3), Ok(()));
+    let mut iter = 4..6;
+    assert_eq!(prod.push_iter(&mut iter), 2);
+    assert_eq!(iter.next(), None);
+    assert_eq!(prod.discarded(), 4);
+
+    let mut buffer = [0; 3];
+    assert_eq!(cons.pop_slice(&mut buffer), 2);
+    assert_eq!(buffer[..2], [0, 1]);
+}
+
+#[test]
+fn overwrite_oldest() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(3)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    assert_eq!(cons.pop(), Some(0));
+    // Make contents wrap around.
+    assert_eq!(prod.push_slice(&[2, 3, 4]), 3);
+    assert_eq!(prod.discarded(), 1);
+    assert_eq!(prod.push(5), Ok(()));
+    assert_eq!(prod.push_iter(&mut (6..8)), 2);
+    assert_eq!(prod.push_slice(&[8, 9, 10, 11, 12]), 5);
+    assert_eq!(prod.discarded(), 9);
+
+    assert_eq!(cons.iter().cloned().collect::<Vec<_>>(), [10, 11, 12]);
+    // Borrowed slices must not be overwritten.
+    let (left, right) = cons.as_slices();
+    assert_eq!(prod.push(13), Err(13));
+    assert_eq!(left.len() + right.len(), 3);
+
+    assert_eq!(cons.pop(), Some(10));
+    assert_eq!(prod.push_slice(&[13, 14]), 2);
+    assert_eq!(cons.pop_slice(&mut [0; 4]), 3);
+}
+
+#[test]
+fn overwrite_drops() {
+    use std::{cell::RefCell, collections::HashSet};
+
+    use super::drop::Dropper;
+
+    let set = RefCell::new(HashSet::new());
+    let (mut prod, mut cons) = RingBuffer::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    for id in 0..4 {
+        prod.push(Dropper::new(&set, id)).unwrap();
+    }
+    assert_eq!(*set.borrow(), [2, 3].iter().cloned().collect());
+    assert_eq!(cons.pop().unwrap().id, 2);
+}
+
+#[test]
+fn block() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(2)
+        .with_policy(Policy::Block(None))
+        .split();
+
+    let pjh = thread::spawn(move || {
+        assert_eq!(prod.push_slice(&[0, 1, 2, 3]), 4);
+        prod.push(4).unwrap();
+        prod.write_all(&[5, 6, 7]).unwrap();
+        assert_eq!(prod.push_iter(&mut (8..10)), 2);
+    });
+
+    let mut bytes = Vec::new();
+    while bytes.len() < 10 {
+        let mut buffer = [0; 4];
+        let n = cons.read(&mut buffer).unwrap_or(0);
+        bytes.extend_from_slice(&buffer[..n]);
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(bytes, (0..10).collect::<Vec<_>>());
+
+    pjh.join().unwrap();
+}
+
+#[test]
+fn block_closed() {
+    let (mut prod, cons) = RingBuffer::<u8>::new(2)
+        .with_policy(Policy::Block(None))
+        .split();
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+
+    let cjh = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        drop(cons);
+    });
+    // The producer waiting for space is woken up when the consumer is dropped.
+    assert_eq!(prod.push(2), Err(2));
+    assert_eq!(prod.push_slice(&[2, 3]), 0);
+    assert!(!prod.push_pod(&0u16));
+
+    cjh.join().unwrap();
+}
+
+#[test]
+fn block_timeout() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(2)
+        .with_policy(Policy::Block(Some(Duration::from_millis(10))))
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 2);
+    assert_eq!(prod.push(3), Err(3));
+    assert_eq!(prod.read_from(&mut &[4, 5][..], None).unwrap(), 0);
+
+    assert_eq!(cons.pop(), Some(0));
+    assert_eq!(prod.read_from(&mut &[4, 5][..], None).unwrap(), 1);
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+}
+
+#[test]
+fn read_from() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    assert_eq!(prod.read_from(&mut &[2][..], Some(1)).unwrap(), 1);
+    assert_eq!(cons.pop(), Some(1));
+
+    let (mut prod, _cons) = RingBuffer::<u8>::new(2)
+        .with_policy(Policy::DropNewest)
+        .split();
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    assert_eq!(prod.read_from(&mut &[2, 3, 4][..], None).unwrap(), 3);
+    assert_eq!(prod.discarded(), 3);
+}
+
+#[test]
+fn drop_newest_unbounded() {
+    let (mut prod, _cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::DropNewest)
+        .split();
+
+    assert_eq!(prod.push_each(|| Some(0)), 3);
+    let mut iter = 1..;
+    assert_eq!(prod.push_iter(&mut iter), 1);
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(prod.discarded(), 2);
+}
+
+#[test]
+fn overwrite_pinned() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    let (left, _) = cons.as_slices();
+    assert_eq!(left, [0, 1]);
+    // Pinned elements are left in place, as with `Reject` policy.
+    assert_eq!(prod.push_slice(&[2, 3]), 0);
+    assert_eq!(prod.push_iter(&mut (2..4)), 0);
+    assert_eq!(prod.discarded(), 0);
+
+    assert_eq!(cons.pop(), Some(0));
+    assert_eq!(prod.push_slice(&[2, 3]), 2);
+    assert_eq!(prod.discarded(), 1);
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+}
+
+#[test]
+fn overwrite_debug() {
+    let (mut prod, cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    assert_eq!(
+        format!("{:?}", cons),
+        "Consumer { capacity: 2, len: 2, elements: [0, 1] }"
+    );
+    // Formatting doesn't pin the elements.
+    assert_eq!(prod.push(2), Ok(()));
+    assert_eq!(prod.discarded(), 1);
+    assert_eq!(cons.iter().cloned().collect::<Vec<_>>(), [1, 2]);
+}
+
+#[test]
+fn overwrite_resized() {
+    let (mut prod, cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[2, 3]), 2);
+    assert_eq!(prod.discarded(), 0);
+
+    // The elements of the previous storage are older, so they are dropped first.
+    assert_eq!(prod.push(4), Ok(()));
+    assert_eq!(prod.discarded(), 3);
+    assert_eq!(cons.iter().cloned().collect::<Vec<_>>(), [3, 4]);
+    assert_eq!(cons.len(), 2);
+}
+
+#[test]
+fn read_from_chunk() {
+    let (mut prod, cons) = RingBuffer::<u8>::new(300)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+    assert_eq!(prod.push_slice(&[0; 300]), 300);
+
+    // Room is made for a bounded chunk rather than for the whole buffer.
+    let n = prod.read_from(&mut &[1; 10][..], None).unwrap();
+    assert!(n > 0);
+    assert_eq!(prod.discarded(), 256);
+    assert_eq!(cons.len(), 44 + n);
+}
@@ -0,0 +1,47 @@
+use crate::RingBuffer;
+
+/// Ring buffer containing `elems` split by the wrap boundary.
+fn wrapped(elems: &[i32]) -> (crate::Producer<i32>, crate::Consumer<i32>) {
+    let (mut prod, mut cons) = RingBuffer::new(elems.len()).split();
+    for _ in 0..(elems.len() / 2) {
+        prod.push(0).unwrap();
+        cons.pop().unwrap();
+    }
+    assert_eq!(prod.push_slice(elems), elems.len());
+    let (left, right) = cons.as_slices();
+    assert!(!left.is_empty() && !right.is_empty());
+    (prod, cons)
+}
+
+#[test]
+fn pop_while() {
+    let (_prod, mut cons) = wrapped(&[1, 2, 3, 4, 5, 6]);
+
+    let mut elems = Vec::new();
+    assert_eq!(cons.pop_while(|&x| x < 5, |x| elems.push(x)), 4);
+    assert_eq!(elems, [1, 2, 3, 4]);
+    assert_eq!(cons.pop_while(|&x| x < 5, |x| elems.push(x)), 0);
+    assert_eq!(cons.pop(), Some(5));
+}
+
+#[test]
+fn discard() {
+    let (_prod, mut cons) = wrapped(&[1, 2, 0xAA, 3, 0xAA, 4]);
+
+    assert_eq!(cons.discard_until(|&x| x == 0xAA), 2);
+    assert_eq!(cons.discard_until(|&x| x == 0xAA), 0);
+    assert_eq!(cons.discard_while(|&x| x == 0xAA), 1);
+    assert_eq!(cons.iter().cloned().collect::<Vec<_>>(), [3, 0xAA, 4]);
+    assert_eq!(cons.discard_until(|&x| x > 0xFF), 3);
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn position() {
+    let (_prod, cons) = wrapped(&[1, 2, 3, 4, 5]);
+
+    assert_eq!(cons.position(|&x| x == 1), Some(0));
+    assert_eq!(cons.position(|&x| x == 5), Some(4));
+    assert_eq!(cons.position(|&x| x == 6), None);
+    assert_eq!(cons.len(), 5);
+}
@@ -0,0 +1,216 @@
+use std::{cell::RefCell, collections::HashSet, thread, time::Duration};
+
+use super::drop::Dropper;
+use crate::RingBuffer;
+
+#[test]
+fn grow() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(3).split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert!(prod.is_full());
+
+    prod.resize(5);
+    assert_eq!(prod.capacity(), 5);
+    assert_eq!(cons.capacity(), 5);
+    assert_eq!(prod.push_slice(&[3, 4, 5, 6, 7, 8]), 5);
+
+    assert_eq!(cons.len(), 8);
+    assert_eq!(prod.len(), 8);
+    assert!(cons.is_full());
+    assert_eq!(cons.remaining(), 0);
+
+    let mut tmp = [0; 8];
+    assert_eq!(cons.pop_slice(&mut tmp), 8);
+    assert_eq!(tmp, [0, 1, 2, 3, 4, 5, 6, 7]);
+    assert!(cons.is_empty());
+    assert!(prod.is_empty());
+}
+
+#[test]
+fn shrink() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(4).split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    prod.resize(1);
+    assert_eq!(prod.push_slice(&[3, 4]), 1);
+
+    assert_eq!(cons.capacity(), 1);
+    assert_eq!(cons.len(), 4);
+    assert_eq!(cons.iter().cloned().collect::<Vec<_>>(), [0, 1, 2]);
+
+    assert_eq!(cons.pop(), Some(0));
+    assert_eq!(cons.pop(), Some(1));
+    assert_eq!(cons.pop(), Some(2));
+    assert_eq!(cons.iter().cloned().collect::<Vec<_>>(), [3]);
+    assert_eq!(cons.pop(), Some(3));
+    assert_eq!(cons.pop(), None);
+
+    assert_eq!(prod.push_slice(&[4, 5]), 1);
+    assert_eq!(cons.pop(), Some(4));
+}
+
+#[test]
+fn multiple() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[2]), 1);
+    prod.resize(3);
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[3, 4, 5]), 2);
+
+    assert!(!cons.is_empty());
+    assert_eq!(cons.len(), 5);
+    assert_eq!(prod.len(), 5);
+    assert_eq!(cons.rb.chain().count(), 4);
+
+    let mut vec = Vec::new();
+    cons.pop_each(
+        |x| {
+            vec.push(x);
+            true
+        },
+        None,
+    );
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+    assert_eq!(cons.rb.chain().count(), 1);
+    assert!(prod.is_empty());
+}
+
+#[test]
+fn empty_storage() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+
+    prod.resize(2);
+    prod.push(0).unwrap();
+
+    assert_eq!(cons.as_slices(), (&[0][..], &[][..]));
+    assert_eq!(cons.pop(), Some(0));
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn drop_pending() {
+    let set = RefCell::new(HashSet::new());
+
+    {
+        let (mut prod, mut cons) = RingBuffer::new(2).split();
+
+        prod.push(Dropper::new(&set, 1)).unwrap();
+        prod.push(Dropper::new(&set, 2)).unwrap();
+        prod.resize(3);
+        prod.push(Dropper::new(&set, 3)).unwrap();
+        prod.resize(1);
+        prod.push(Dropper::new(&set, 4)).unwrap();
+        assert_eq!(set.borrow().len(), 4);
+
+        assert_eq!(cons.pop().unwrap().id, 1);
+        assert_eq!(set.borrow().len(), 3);
+        assert_eq!(cons.discard(2), 2);
+        assert_eq!(set.borrow().len(), 1);
+
+        drop(cons);
+        assert_eq!(set.borrow().len(), 1);
+        prod.push(Dropper::new(&set, 5)).unwrap_err();
+    }
+
+    assert_eq!(set.borrow().len(), 0);
+}
+
+#[test]
+fn drop_halves() {
+    let set = RefCell::new(HashSet::new());
+
+    {
+        let (mut prod, mut cons) = RingBuffer::new(2).split();
+
+        prod.push(Dropper::new(&set, 1)).unwrap();
+        prod.resize(2);
+        prod.push(Dropper::new(&set, 2)).unwrap();
+        drop(prod);
+        assert_eq!(set.borrow().len(), 2);
+
+        assert_eq!(cons.pop().unwrap().id, 1);
+        assert_eq!(cons.pop().unwrap().id, 2);
+        assert!(cons.pop().is_none());
+    }
+
+    assert_eq!(set.borrow().len(), 0);
+}
+
+#[test]
+fn threads() {
+    const COUNT: usize = 10_000;
+    let (mut prod, mut cons) = RingBuffer::<usize>::new(4).split();
+
+    let pjh = thread::spawn(move || {
+        for i in 0..COUNT {
+            if i % 1000 == 999 {
+                prod.resize(4 + i % 7);
+            }
+            while prod.push(i).is_err() {
+                thread::sleep(Duration::from_micros(10));
+            }
+        }
+    });
+
+    let mut next = 0;
+    while next < COUNT {
+        match cons.pop() {
+            Some(x) => {
+                assert_eq!(x, next);
+                next += 1;
+            }
+            None => thread::sleep(Duration::from_micros(10)),
+        }
+    }
+
+    pjh.join().unwrap();
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn bulk() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+    let (mut dst, out) = RingBuffer::<i32>::new(8).split();
+
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[2, 3]), 2);
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[4, 5]), 2);
+
+    assert_eq!(cons.discard(3), 3);
+    assert_eq!(cons.pop_while(|x| *x < 4, drop), 1);
+    assert_eq!(cons.pop(), Some(4));
+    assert_eq!(cons.pop(), Some(5));
+
+    assert_eq!(prod.push_slice(&[6, 7]), 2);
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[8, 9]), 2);
+    assert_eq!(cons.move_to(&mut dst, None), 4);
+    assert_eq!(out.iter().cloned().collect::<Vec<_>>(), [6, 7, 8, 9]);
+    assert!(prod.is_empty());
+}
+
+#[test]
+fn views() {
+    let (mut prod, cons) = RingBuffer::<i32>::new(4).split();
+    assert_eq!(prod.push_slice(&[0, 1, 2, 3]), 4);
+    prod.resize(2);
+    assert_eq!(prod.push(4), Ok(()));
+
+    assert_eq!(cons.len(), 5);
+    assert_eq!(
+        format!("{:?}", cons),
+        "Consumer { capacity: 2, len: 5, elements: [0, 1, 2, 3, 4] }"
+    );
+    assert_eq!(cons.position(|x| *x == 4), Some(4));
+    let mut visited = Vec::new();
+    cons.for_each(|x| visited.push(*x));
+    assert_eq!(visited, [0, 1, 2, 3, 4]);
+    // Slices cover the oldest storage only.
+    assert_eq!(cons.as_slices(), (&[0, 1, 2, 3][..], &[][..]));
+}
@@ -0,0 +1,70 @@
+use crate::{Consumer, RingBuffer};
+
+/// Consumer of `bytes` stored starting from `offset` in the ring buffer memory.
+fn stored(bytes: &[u8], capacity: usize, offset: usize) -> Consumer<u8> {
+    let (mut prod, mut cons) = RingBuffer::new(capacity).split();
+    for _ in 0..offset {
+        prod.push(0).unwrap();
+        cons.pop().unwrap();
+    }
+    assert_eq!(prod.push_slice(bytes), bytes.len());
+    cons
+}
+
+#[test]
+fn find_byte() {
+    let bytes = (0..40).collect::<Vec<u8>>();
+    for offset in 0..41 {
+        let cons = stored(&bytes, 40, offset);
+        for &b in &bytes {
+            assert_eq!(cons.find_byte(b), Some(b as usize));
+        }
+        assert_eq!(cons.find_byte(40), None);
+        assert_eq!(cons.find_byte(0x80), None);
+    }
+}
+
+#[test]
+fn find_subslice() {
+    let bytes = b"abc\r\ndef\r\n";
+    for offset in 0..11 {
+        let cons = stored(bytes, 10, offset);
+        assert_eq!(cons.find_subslice(b"\r\n"), Some(3));
+        assert_eq!(cons.find_subslice(b"def\r\n"), Some(5));
+        assert_eq!(cons.find_subslice(b"\n\r"), None);
+        assert_eq!(cons.find_subslice(b"\r\n\r"), None);
+        assert_eq!(cons.find_subslice(b""), Some(0));
+        assert_eq!(cons.find_subslice(&bytes[..]), Some(0));
+    }
+}
+
+#[test]
+fn read_until_delim() {
+    // Delimiter straddles the wrap point.
+    let mut cons = stored(b"ab\r\ncd", 6, 4);
+    assert_eq!(cons.as_slices().0, b"ab\r");
+
+    let mut line = Vec::new();
+    assert_eq!(cons.read_until_delim(b"\r\n", &mut line), Some(4));
+    assert_eq!(line, b"ab\r\n");
+    assert_eq!(cons.read_until_delim(b"\r\n", &mut line), None);
+    assert_eq!(cons.len(), 2);
+    assert_eq!(cons.read_until_delim(b"d", &mut line), Some(2));
+    assert_eq!(line, b"ab\r\ncd");
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(3).split();
+    prod.push_slice(b"ab\r");
+    prod.resize(4);
+    prod.push_slice(b"\nc");
+    assert_eq!(cons.find_byte(b'\n'), Some(3));
+    assert_eq!(cons.find_subslice(b"\r\nc"), Some(2));
+
+    let mut line = Vec::new();
+    assert_eq!(cons.read_until_delim(b"\r\n", &mut line), Some(4));
+    assert_eq!(line, b"ab\r\n");
+    assert_eq!(cons.len(), 1);
+}
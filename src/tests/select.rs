@@ -0,0 +1,101 @@
+use std::{thread, time::Duration};
+
+use crate::{RingBuffer, Select};
+
+#[test]
+fn timeout() {
+    let (_prod, cons) = RingBuffer::<i32>::new(2).split();
+
+    let mut select = Select::new();
+    assert_eq!(select.add(&cons), 0);
+    assert_eq!(select.try_ready(), None);
+    assert_eq!(select.ready_timeout(Duration::from_millis(10)), None);
+}
+
+#[test]
+fn round_robin() {
+    let (mut prod_a, mut cons_a) = RingBuffer::<i32>::new(4).split();
+    let (mut prod_b, mut cons_b) = RingBuffer::<u8>::new(4).split();
+
+    prod_a.push_slice(&[0, 1]);
+    prod_b.push_slice(&[0, 1]);
+
+    let mut select = Select::new();
+    let a = select.add(&cons_a);
+    let b = select.add(&cons_b);
+
+    assert_eq!(select.ready(), a);
+    assert_eq!(select.ready(), b);
+    assert_eq!(select.ready(), a);
+
+    cons_a.pop_slice(&mut [0; 2]);
+    assert_eq!(select.ready(), b);
+    assert_eq!(select.ready(), b);
+
+    cons_b.pop_slice(&mut [0; 2]);
+    assert_eq!(select.try_ready(), None);
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(1).split();
+
+    let mut select = Select::new();
+    select.add(&cons);
+
+    prod.resize(2);
+    prod.push(0).unwrap();
+    assert_eq!(select.ready(), 0);
+    assert_eq!(cons.pop(), Some(0));
+
+    let pjh = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        prod.resize(3);
+        prod.push(1).unwrap();
+    });
+    assert_eq!(select.ready(), 0);
+    assert_eq!(cons.pop(), Some(1));
+
+    pjh.join().unwrap();
+}
+
+#[test]
+fn threads() {
+    const COUNT: usize = 1000;
+    const THREADS: usize = 4;
+
+    let mut conses = Vec::new();
+    let mut pjhs = Vec::new();
+    for k in 0..THREADS {
+        let (mut prod, cons) = RingBuffer::<usize>::new(4).split();
+        conses.push(cons);
+        pjhs.push(thread::spawn(move || {
+            for i in 0..COUNT {
+                while prod.push(k * COUNT + i).is_err() {
+                    thread::yield_now();
+                }
+                if i % 100 == 0 {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+        }));
+    }
+
+    let mut select = Select::new();
+    for cons in &conses {
+        select.add(cons);
+    }
+
+    let mut next = [0; THREADS];
+    while next.iter().any(|&n| n < COUNT) {
+        let k = select.ready_timeout(Duration::from_secs(10)).unwrap();
+        while let Some(x) = conses[k].pop() {
+            assert_eq!(x, k * COUNT + next[k]);
+            next[k] += 1;
+        }
+    }
+
+    for pjh in pjhs {
+        pjh.join().unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{Policy, RingBuffer};
+
+#[test]
+fn push_pop() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+    assert_eq!(prod.next_seq(), 0);
+    assert_eq!(cons.next_seq(), 0);
+
+    assert_eq!(prod.push_with_seq(10), Ok(0));
+    assert_eq!(prod.push_slice(&[11]), 1);
+    assert_eq!(prod.push_with_seq(12), Err(12));
+    assert_eq!(prod.next_seq(), 2);
+
+    assert_eq!(cons.get_by_seq(0), Some(&10));
+    assert_eq!(cons.get_by_seq(1), Some(&11));
+    assert_eq!(cons.get_by_seq(2), None);
+
+    assert_eq!(cons.pop_with_seq(), Some((0, 10)));
+    assert_eq!(cons.get_by_seq(0), None);
+    assert_eq!(prod.push_with_seq(12), Ok(2));
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+    assert_eq!(cons.next_seq(), 3);
+    assert_eq!(cons.pop_with_seq(), None);
+}
+
+#[test]
+fn gaps() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2, 3, 4]), 5);
+    assert_eq!(cons.pop_with_seq(), Some((3, 3)));
+    assert_eq!(cons.get_by_seq(4), Some(&4));
+
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::DropNewest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(prod.push_with_seq(3), Err(3));
+    assert_eq!(cons.pop_with_seq(), Some((0, 0)));
+    assert_eq!(prod.push_with_seq(3), Ok(2));
+    assert_eq!(cons.get_by_seq(2), Some(&3));
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::from_vec(vec![0, 1], 2).split();
+    assert_eq!(prod.next_seq(), 2);
+    prod.resize(4);
+    assert_eq!(prod.push_with_seq(2), Ok(2));
+    assert_eq!(cons.get_by_seq(0), Some(&0));
+    assert_eq!(cons.get_by_seq(2), Some(&2));
+    assert_eq!(cons.get_by_seq(3), None);
+    assert_eq!(cons.pop_with_seq(), Some((0, 0)));
+    assert_eq!(cons.pop_with_seq(), Some((1, 1)));
+    assert_eq!(cons.pop_with_seq(), Some((2, 2)));
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Deserializer};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
+
+use crate::{RingBuffer, Snapshot};
+
+fn tokens(capacity: usize, elements: &[i32]) -> Vec<Token> {
+    let mut tokens = vec![
+        Token::Struct {
+            name: "RingBuffer",
+            len: 2,
+        },
+        Token::Str("capacity"),
+        Token::U64(capacity as u64),
+        Token::Str("elements"),
+        Token::Seq {
+            len: Some(elements.len()),
+        },
+    ];
+    tokens.extend(elements.iter().map(|x| Token::I32(*x)));
+    tokens.extend_from_slice(&[Token::SeqEnd, Token::StructEnd]);
+    tokens
+}
+
+/// Capacity and elements of deserialized ring buffer.
+#[derive(Debug, PartialEq)]
+struct Contents(usize, Vec<i32>);
+
+impl<'de> Deserialize<'de> for Contents {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rb = RingBuffer::<i32>::deserialize(deserializer)?;
+        let capacity = rb.capacity();
+        let (_, mut cons) = rb.split();
+        let mut elems = Vec::new();
+        while let Some(x) = cons.pop() {
+            elems.push(x);
+        }
+        Ok(Contents(capacity, elems))
+    }
+}
+
+#[test]
+fn ring_buffer() {
+    let rb = RingBuffer::new(4).preload(&mut vec![1, 2, 3].into_iter());
+    assert_ser_tokens(&rb, &tokens(4, &[1, 2, 3]));
+
+    let rb = RingBuffer::<i32>::new(2);
+    assert_ser_tokens(&rb, &tokens(2, &[]));
+}
+
+#[test]
+fn snapshot_wrapped() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(3).split();
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+    assert_eq!(prod.push_slice(&[3, 4]), 2);
+
+    assert_ser_tokens(&cons.snapshot(), &tokens(3, &[2, 3, 4]));
+    cons.access(|left, right| {
+        assert_ser_tokens(&Snapshot::new(3, left, right), &tokens(3, &[2, 3, 4]));
+    });
+}
+
+#[test]
+fn round_trip() {
+    assert_de_tokens(&Contents(0, vec![]), &tokens(0, &[]));
+
+    let (mut prod, cons) = RingBuffer::<i32>::new(3).split();
+    assert_eq!(prod.push_slice(&[5, 6]), 2);
+
+    let tokens = tokens(3, &[5, 6]);
+    assert_ser_tokens(&cons.snapshot(), &tokens);
+    assert_de_tokens(&Contents(3, vec![5, 6]), &tokens);
+}
+
+#[test]
+fn deserialize_seq() {
+    assert_de_tokens(
+        &Contents(2, vec![7]),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U64(2),
+            Token::Seq { len: Some(1) },
+            Token::I32(7),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn too_many_elements() {
+    assert_de_tokens_error::<RingBuffer<i32>>(
+        &tokens(1, &[1, 2]),
+        "invalid length 2, expected number of elements not exceeding capacity",
+    );
+}
+
+#[test]
+fn too_large_capacity() {
+    for &capacity in [usize::MAX, usize::MAX / 2].iter() {
+        assert_de_tokens_error::<RingBuffer<i32>>(
+            &tokens(capacity, &[1]),
+            &format!(
+                "invalid value: integer `{}`, expected capacity of ring buffer that can be allocated",
+                capacity
+            ),
+        );
+    }
+}
+
+#[test]
+fn snapshot_resized() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(4).split();
+    assert_eq!(prod.push_slice(&[0, 1, 2, 3]), 4);
+    assert_eq!(cons.pop(), Some(0));
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[4, 5, 6]), 2);
+
+    let tokens = tokens(5, &[1, 2, 3, 4, 5]);
+    assert_ser_tokens(&cons.snapshot(), &tokens);
+    assert_de_tokens(&Contents(5, vec![1, 2, 3, 4, 5]), &tokens);
+}
@@ -0,0 +1,97 @@
+use crate::{Policy, RingBuffer, Stats};
+
+#[test]
+fn push_pop() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(3).split();
+
+    assert_eq!(prod.stats(), Stats::default());
+
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    assert_eq!(cons.pop(), Some(0));
+    // The last element doesn't fit, so both pushes fail.
+    assert_eq!(prod.push_slice(&[2, 3, 4]), 2);
+    assert_eq!(prod.push(4), Err(4));
+    assert_eq!(cons.pop_slice(&mut [0; 4]), 3);
+    assert_eq!(cons.pop(), None);
+
+    let stats = cons.stats();
+    assert_eq!(stats.pushed, 4);
+    assert_eq!(stats.popped, 4);
+    assert_eq!(stats.push_failures, 2);
+    assert_eq!(stats.pop_failures, 1);
+    assert_eq!(stats.peak_len, 3);
+    assert_eq!(stats.wraps, 1);
+    assert_eq!(prod.stats(), stats);
+}
+
+#[test]
+fn wraps() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+
+    for i in 0..7 {
+        prod.push(i).unwrap();
+        assert_eq!(cons.pop(), Some(i));
+    }
+
+    let stats = prod.stats();
+    assert_eq!(stats.pushed, 7);
+    assert_eq!(stats.peak_len, 1);
+    assert_eq!(stats.wraps, 2);
+}
+
+#[test]
+fn unsplit() {
+    let rb = RingBuffer::<i32>::new(2);
+    assert_eq!(rb.stats(), Stats::default());
+}
+
+#[test]
+fn overwrite() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(cons.pop(), Some(1));
+
+    // Evicted elements are counted as popped, so the counters agree with the length.
+    let stats = prod.stats();
+    assert_eq!(stats.pushed, 3);
+    assert_eq!(stats.popped, 2);
+    assert_eq!(stats.peak_len, 2);
+}
+
+#[test]
+fn resize() {
+    let (mut prod, _cons) = RingBuffer::<i32>::new(3).split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    prod.resize(2);
+    assert_eq!(prod.push_slice(&[3, 4]), 2);
+    assert_eq!(prod.len(), 5);
+    assert_eq!(prod.stats().peak_len, 5);
+}
+
+#[test]
+fn push_failures() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(1)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+
+    assert_eq!(prod.push(0), Ok(()));
+    // The element is stored by overwriting the oldest one, so nothing has failed.
+    assert_eq!(prod.push(1), Ok(()));
+    assert_eq!(prod.stats().push_failures, 0);
+
+    let _slices = cons.as_slices();
+    assert_eq!(prod.push(2), Err(2));
+    assert_eq!(prod.stats().push_failures, 1);
+    assert_eq!(cons.pop(), Some(1));
+
+    let (mut prod, _cons) = RingBuffer::<i32>::new(1)
+        .with_policy(Policy::DropNewest)
+        .split();
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(prod.push(3), Ok(()));
+    assert_eq!(prod.stats().push_failures, 2);
+}
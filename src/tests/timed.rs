@@ -0,0 +1,78 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use crate::{Clock, RingBuffer};
+
+/// Clock counting milliseconds manually.
+#[derive(Clone, Default)]
+struct ManualClock(Rc<Cell<u64>>);
+
+impl ManualClock {
+    fn advance(&self, ms: u64) {
+        self.0.set(self.0.get() + ms);
+    }
+}
+
+impl Clock for ManualClock {
+    type Instant = u64;
+
+    fn now(&self) -> u64 {
+        self.0.get()
+    }
+
+    fn since(&self, earlier: u64) -> Duration {
+        Duration::from_millis(self.0.get() - earlier)
+    }
+}
+
+#[test]
+fn age() {
+    let clock = ManualClock::default();
+    let (mut prod, mut cons) = RingBuffer::<i32>::split_timed_with_clock(3, clock.clone());
+
+    assert_eq!(cons.oldest_age(), None);
+    prod.push(0).unwrap();
+    clock.advance(10);
+    prod.push(1).unwrap();
+    clock.advance(5);
+
+    assert_eq!(cons.oldest_age(), Some(Duration::from_millis(15)));
+    assert_eq!(cons.pop_with_age(), Some((0, Duration::from_millis(15))));
+    assert_eq!(cons.oldest_age(), Some(Duration::from_millis(5)));
+    assert_eq!(cons.pop(), Some(1));
+    assert_eq!(cons.pop_with_age(), None);
+}
+
+#[test]
+fn discard() {
+    let clock = ManualClock::default();
+    let (mut prod, mut cons) = RingBuffer::<i32>::split_timed_with_clock(4, clock.clone());
+
+    // Make contents wrap around.
+    assert_eq!(prod.push_iter(&mut (0..3)), 3);
+    assert_eq!(cons.discard_older_than(Duration::from_millis(0)), 0);
+    clock.advance(1);
+    assert_eq!(cons.discard_older_than(Duration::from_millis(0)), 3);
+
+    for i in 0..4 {
+        prod.push(i).unwrap();
+        clock.advance(10);
+    }
+    assert!(prod.is_full());
+    assert_eq!(prod.push(4), Err(4));
+
+    assert_eq!(cons.discard_older_than(Duration::from_millis(40)), 0);
+    assert_eq!(cons.discard_older_than(Duration::from_millis(25)), 2);
+    assert_eq!(cons.len(), 2);
+    assert_eq!(cons.pop_with_age(), Some((2, Duration::from_millis(20))));
+    assert_eq!(cons.discard_older_than(Duration::from_millis(5)), 1);
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn monotonic() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::split_timed(2);
+    prod.push(0).unwrap();
+    let (elem, age) = cons.pop_with_age().unwrap();
+    assert_eq!(elem, 0);
+    assert!(age < Duration::from_secs(60));
+}
@@ -0,0 +1,117 @@
+use std::mem::MaybeUninit;
+
+use crate::{move_items, Producer, RbBase, RbConsumer, RbProducer, RingBuffer};
+
+/// Producer wrapper counting elements been pushed through it.
+struct Counting<T> {
+    inner: Producer<T>,
+    count: usize,
+}
+
+impl<T> RbBase<T> for Counting<T> {
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<T> RbProducer<T> for Counting<T> {
+    unsafe fn push_access<F>(&mut self, f: F) -> usize
+    where
+        F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize,
+    {
+        let n = self.inner.push_access(f);
+        self.count += n;
+        n
+    }
+}
+
+fn fill<T, P: RbProducer<T>>(prod: &mut P, mut f: impl FnMut() -> T) -> usize {
+    let mut n = 0;
+    while !prod.is_full() {
+        if prod.push(f()).is_err() {
+            unreachable!();
+        }
+        n += 1;
+    }
+    n
+}
+
+fn sum<C: RbConsumer<i32>>(cons: &C) -> i32 {
+    cons.iter().sum()
+}
+
+#[test]
+fn generic_base() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(3).split();
+
+    assert_eq!(fill(&mut prod, || 1), 3);
+    assert_eq!(RbBase::remaining(&prod), 0);
+    assert_eq!(RbBase::len(&cons), 3);
+    assert!(RbBase::is_full(&cons));
+
+    assert_eq!(RbConsumer::pop(&mut cons), Some(1));
+    assert_eq!(RbBase::remaining(&prod), 1);
+}
+
+#[test]
+fn slices_and_iter() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(4).split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2, 3]), 4);
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+    assert_eq!(prod.push_slice(&[4, 5]), 2);
+
+    assert_eq!(cons.as_slices(), (&[2, 3, 4][..], &[5][..]));
+    assert_eq!(sum(&cons), 14);
+
+    for x in cons.iter_mut() {
+        *x *= 10;
+    }
+    assert_eq!(cons.iter().cloned().collect::<Vec<_>>(), [20, 30, 40, 50]);
+
+    let (left, right) = cons.as_mut_slices();
+    left[0] = 0;
+    right[0] = 0;
+    assert_eq!(sum(&cons), 70);
+}
+
+#[test]
+fn move_through_wrapper() {
+    let (mut prod0, mut cons0) = RingBuffer::<i32>::new(4).split();
+    let (prod1, cons1) = RingBuffer::<i32>::new(4).split();
+    let mut prod1 = Counting {
+        inner: prod1,
+        count: 0,
+    };
+
+    assert_eq!(prod0.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(move_items(&mut cons0, &mut prod1, None), 3);
+    assert_eq!(prod1.count, 3);
+
+    assert_eq!(prod0.push_slice(&[3, 4]), 2);
+    assert_eq!(cons0.move_to(&mut prod1, Some(1)), 1);
+    assert_eq!(prod1.count, 4);
+
+    assert_eq!(cons1.iter().cloned().collect::<Vec<_>>(), [0, 1, 2, 3]);
+    assert_eq!(cons0.iter().cloned().collect::<Vec<_>>(), [4]);
+}
+
+#[test]
+fn resized_remaining() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(4).split();
+    assert_eq!(prod.push_slice(&[1, 2, 3, 4]), 4);
+    prod.resize(2);
+    assert_eq!(RbBase::len(&prod), 4);
+    assert_eq!(RbBase::remaining(&prod), 2);
+
+    let (mut dst_prod, _dst_cons) = RingBuffer::<i32>::new(4).split();
+    assert_eq!(dst_prod.push_slice(&[0; 4]), 4);
+    dst_prod.resize(8);
+    assert_eq!(RbBase::remaining(&dst_prod), 8);
+    assert_eq!(move_items(&mut cons, &mut dst_prod, None), 4);
+    assert_eq!(RbBase::remaining(&dst_prod), 4);
+}
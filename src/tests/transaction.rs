@@ -0,0 +1,96 @@
+use std::io::Read;
+
+use crate::{Policy, RingBuffer};
+
+#[test]
+fn commit() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(4).split();
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert_eq!(cons.pop(), Some(0));
+    // Make contents wrap around.
+    assert_eq!(prod.push_slice(&[3, 4]), 2);
+
+    let mut tx = cons.begin();
+    assert_eq!(tx.pop(), Some(1));
+    let mut elems = [0; 3];
+    assert_eq!(tx.pop_slice(&mut elems), 3);
+    assert_eq!(elems, [2, 3, 4]);
+    assert_eq!(tx.pop(), None);
+    assert!(tx.is_empty());
+    assert_eq!(tx.offset(), 4);
+    assert_eq!(tx.commit(), 4);
+
+    assert!(cons.is_empty());
+    assert!(prod.is_empty());
+}
+
+#[test]
+fn rollback() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(4).split();
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+
+    let mut tx = cons.begin();
+    assert_eq!(tx.pop(), Some(0));
+    assert_eq!(tx.len(), 2);
+    tx.rollback();
+
+    {
+        let mut tx = cons.begin();
+        assert_eq!(tx.pop_slice(&mut [0; 2]), 2);
+    }
+
+    let mut tx = cons.begin();
+    assert_eq!(tx.pop(), Some(0));
+    assert_eq!(tx.commit(), 1);
+    assert_eq!(cons.pop_slice(&mut [0; 4]), 2);
+}
+
+#[test]
+fn read() {
+    let (mut prod, mut cons) = RingBuffer::<u8>::new(4).split();
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+
+    let mut tx = cons.begin();
+    let mut bytes = [0; 2];
+    tx.read_exact(&mut bytes).unwrap();
+    assert_eq!(bytes, [0, 1]);
+    tx.commit();
+
+    let mut tx = cons.begin();
+    assert!(tx.read_exact(&mut bytes).is_err());
+    tx.rollback();
+
+    assert_eq!(cons.pop(), Some(2));
+}
+
+#[test]
+fn resize() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2).split();
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+    prod.resize(4);
+    assert_eq!(prod.push_slice(&[2, 3]), 2);
+
+    let mut tx = cons.begin();
+    let mut elems = [0; 4];
+    assert_eq!(tx.pop_slice(&mut elems), 4);
+    assert_eq!(elems, [0, 1, 2, 3]);
+    assert_eq!(tx.commit(), 4);
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn overwrite() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(2)
+        .with_policy(Policy::OverwriteOldest)
+        .split();
+    assert_eq!(prod.push_slice(&[0, 1]), 2);
+
+    let mut tx = cons.begin();
+    assert_eq!(tx.pop(), Some(0));
+    assert_eq!(prod.push(2), Err(2));
+    assert_eq!(tx.commit(), 1);
+
+    assert_eq!(prod.push(2), Ok(()));
+    assert_eq!(prod.push(3), Ok(()));
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+}
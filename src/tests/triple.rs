@@ -0,0 +1,46 @@
+use std::thread;
+
+use crate::TripleBuffer;
+
+#[test]
+fn latest() {
+    let (mut prod, mut cons) = TripleBuffer::new(0).split();
+    assert!(!cons.updated());
+    assert_eq!(*cons.read(), 0);
+
+    for i in 1..5 {
+        prod.write(i);
+    }
+    assert!(cons.updated());
+    assert_eq!(*cons.read(), 4);
+    assert!(!cons.updated());
+
+    prod.write(5);
+    assert_eq!(*cons.read(), 5);
+    prod.write(6);
+    prod.write(7);
+    assert_eq!(*cons.read(), 7);
+    assert_eq!(*cons.read(), 7);
+}
+
+#[test]
+fn threads() {
+    const N: usize = 10000;
+    let (mut prod, mut cons) = TripleBuffer::new(vec![0; 4]).split();
+
+    let pjh = thread::spawn(move || {
+        for i in 1..=N {
+            prod.write(vec![i; 4]);
+        }
+    });
+
+    let mut last = 0;
+    while last < N {
+        let value = cons.read();
+        assert!(value.iter().all(|&x| x == value[0]));
+        assert!(value[0] >= last);
+        last = value[0];
+    }
+
+    pjh.join().unwrap();
+}
@@ -0,0 +1,75 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{RingBuffer, Watermark};
+
+fn recorded(low: usize, high: usize) -> (RingBuffer<i32>, Arc<Mutex<Vec<Watermark>>>) {
+    let marks = Arc::new(Mutex::new(Vec::new()));
+    let rb = RingBuffer::new(8).with_watermark_callback(low, high, {
+        let marks = marks.clone();
+        move |mark| marks.lock().unwrap().push(mark)
+    });
+    (rb, marks)
+}
+
+#[test]
+fn edges() {
+    let (rb, marks) = recorded(2, 5);
+    let (mut prod, mut cons) = rb.split();
+
+    assert_eq!(prod.push_slice(&[0; 5]), 5);
+    assert!(!prod.above_watermark());
+    prod.push(0).unwrap();
+    assert!(prod.above_watermark());
+    assert!(cons.above_watermark());
+    assert_eq!(*marks.lock().unwrap(), [Watermark::High]);
+
+    // No more notifications until the low watermark is crossed.
+    prod.push(0).unwrap();
+    assert_eq!(cons.pop_slice(&mut [0; 5]), 5);
+    assert!(cons.above_watermark());
+    cons.pop().unwrap();
+    assert!(!cons.above_watermark());
+    assert!(!prod.above_watermark());
+    assert_eq!(*marks.lock().unwrap(), [Watermark::High, Watermark::Low]);
+
+    cons.pop().unwrap();
+    assert_eq!(prod.push_slice(&[0; 6]), 6);
+    assert_eq!(
+        *marks.lock().unwrap(),
+        [Watermark::High, Watermark::Low, Watermark::High]
+    );
+}
+
+#[test]
+fn flag() {
+    let (mut prod, mut cons) = RingBuffer::<i32>::new(4).with_watermarks(1, 2).split();
+
+    assert_eq!(prod.push_slice(&[0, 1, 2]), 3);
+    assert!(cons.above_watermark());
+    assert_eq!(cons.pop_slice(&mut [0; 2]), 2);
+    assert!(cons.above_watermark());
+    cons.pop().unwrap();
+    assert!(!prod.above_watermark());
+}
+
+#[test]
+fn resize() {
+    let (rb, marks) = recorded(1, 3);
+    let (mut prod, mut cons) = rb.split();
+
+    assert_eq!(prod.push_slice(&[0; 3]), 3);
+    prod.resize(16);
+    prod.push(0).unwrap();
+    assert_eq!(*marks.lock().unwrap(), [Watermark::High]);
+
+    assert_eq!(cons.pop_slice(&mut [0; 3]), 3);
+    assert!(cons.above_watermark());
+    cons.pop().unwrap();
+    assert_eq!(*marks.lock().unwrap(), [Watermark::High, Watermark::Low]);
+}
+
+#[test]
+#[should_panic]
+fn invalid() {
+    let _ = RingBuffer::<i32>::new(4).with_watermarks(3, 2);
+}
@@ -0,0 +1,188 @@
+use std::time::{Duration, Instant};
+
+use crate::{consumer::Consumer, producer::Producer, ring_buffer::RingBuffer};
+
+/// Source of timestamps for [`TimedProducer`](struct.TimedProducer.html).
+pub trait Clock {
+    /// Point in time.
+    type Instant: Copy;
+
+    /// Returns the current time.
+    fn now(&self) -> Self::Instant;
+
+    /// Returns time elapsed from `earlier` till now.
+    fn since(&self, earlier: Self::Instant) -> Duration;
+}
+
+/// Clock based on [`Instant`](https://doc.rust-lang.org/std/time/struct.Instant.html).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MonotonicClock;
+
+impl Clock for MonotonicClock {
+    type Instant = Instant;
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn since(&self, earlier: Instant) -> Duration {
+        Instant::now().saturating_duration_since(earlier)
+    }
+}
+
+/// Element with the time it has been pushed at.
+pub(crate) struct Stamped<T, I> {
+    time: I,
+    elem: T,
+}
+
+/// Producer recording the time each element is pushed at.
+///
+/// Created by [`RingBuffer::split_timed`](struct.RingBuffer.html#method.split_timed).
+pub struct TimedProducer<T, C: Clock = MonotonicClock> {
+    prod: Producer<Stamped<T, C::Instant>>,
+    clock: C,
+}
+
+/// Consumer aware of the time elements have been waiting in the ring buffer.
+///
+/// Created by [`RingBuffer::split_timed`](struct.RingBuffer.html#method.split_timed).
+pub struct TimedConsumer<T, C: Clock = MonotonicClock> {
+    cons: Consumer<Stamped<T, C::Instant>>,
+    clock: C,
+}
+
+impl<T: Sized> RingBuffer<T> {
+    /// Creates a ring buffer of given capacity and splits it into producer and consumer
+    /// timestamping elements by the monotonic clock.
+    pub fn split_timed(capacity: usize) -> (TimedProducer<T>, TimedConsumer<T>) {
+        Self::split_timed_with_clock(capacity, MonotonicClock)
+    }
+
+    /// Creates a ring buffer of given capacity and splits it into producer and consumer
+    /// timestamping elements by the `clock`.
+    ///
+    /// Ages of elements are computed under assumption that the clock is monotonic.
+    pub fn split_timed_with_clock<C: Clock + Clone>(
+        capacity: usize,
+        clock: C,
+    ) -> (TimedProducer<T, C>, TimedConsumer<T, C>) {
+        let (prod, cons) = RingBuffer::new(capacity).split();
+        (
+            TimedProducer {
+                prod,
+                clock: clock.clone(),
+            },
+            TimedConsumer { cons, clock },
+        )
+    }
+}
+
+impl<T: Sized, C: Clock> TimedProducer<T, C> {
+    /// Returns capacity of the ring buffer.
+    pub fn capacity(&self) -> usize {
+        self.prod.capacity()
+    }
+
+    /// The length of the data stored in the buffer.
+    ///
+    /// Actual length may be equal to or less than the returned value.
+    pub fn len(&self) -> usize {
+        self.prod.len()
+    }
+
+    /// Checks if the ring buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.prod.is_empty()
+    }
+
+    /// Checks if the ring buffer is full.
+    pub fn is_full(&self) -> bool {
+        self.prod.is_full()
+    }
+
+    /// The remaining space in the buffer.
+    ///
+    /// Actual remaining space may be equal to or greater than the returning value.
+    pub fn remaining(&self) -> usize {
+        self.prod.remaining()
+    }
+
+    /// Appends an element to the ring buffer stamping it with the current time.
+    /// On failure returns an error containing the element that hasn't beed appended.
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        let time = self.clock.now();
+        self.prod
+            .push(Stamped { time, elem })
+            .map_err(|stamped| stamped.elem)
+    }
+
+    /// Appends elements from an iterator to the ring buffer stamping all of them with the current time.
+    /// Elements that haven't been added to the ring buffer remain in the iterator.
+    ///
+    /// Returns count of elements been appended to the ring buffer.
+    pub fn push_iter<I: Iterator<Item = T>>(&mut self, elems: &mut I) -> usize {
+        let time = self.clock.now();
+        self.prod
+            .push_iter(&mut elems.map(|elem| Stamped { time, elem }))
+    }
+}
+
+impl<T: Sized, C: Clock> TimedConsumer<T, C> {
+    /// Returns capacity of the ring buffer.
+    pub fn capacity(&self) -> usize {
+        self.cons.capacity()
+    }
+
+    /// The length of the data stored in the buffer.
+    ///
+    /// Actual length may be equal to or greater than the returned value.
+    pub fn len(&self) -> usize {
+        self.cons.len()
+    }
+
+    /// Checks if the ring buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+
+    /// Removes the oldest element from the ring buffer and returns it.
+    /// Returns `None` if the ring buffer is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.cons.pop().map(|stamped| stamped.elem)
+    }
+
+    /// Removes the oldest element from the ring buffer and returns it along with the time it has been waiting for.
+    /// Returns `None` if the ring buffer is empty.
+    pub fn pop_with_age(&mut self) -> Option<(T, Duration)> {
+        let clock = &self.clock;
+        self.cons
+            .pop()
+            .map(|stamped| (stamped.elem, clock.since(stamped.time)))
+    }
+
+    /// Returns the time the oldest element has been waiting for.
+    /// Returns `None` if the ring buffer is empty.
+    pub fn oldest_age(&self) -> Option<Duration> {
+        let mut age = None;
+        self.cons.access(|left, _| {
+            age = left.first().map(|stamped| self.clock.since(stamped.time));
+        });
+        age
+    }
+
+    /// Removes and drops elements that have been waiting for longer than `max_age`.
+    ///
+    /// Returns the number of elements removed.
+    pub fn discard_older_than(&mut self, max_age: Duration) -> usize {
+        let mut count = 0;
+        self.cons.access(|left, right| {
+            count = left
+                .iter()
+                .chain(right.iter())
+                .take_while(|stamped| self.clock.since(stamped.time) > max_age)
+                .count();
+        });
+        self.cons.discard(count)
+    }
+}
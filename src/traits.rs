@@ -0,0 +1,127 @@
+use std::{
+    iter::Chain,
+    mem::{self, MaybeUninit},
+    slice,
+};
+
+/// Properties common to both parts of a ring buffer.
+pub trait RbBase<T> {
+    /// Returns capacity of the ring buffer.
+    fn capacity(&self) -> usize;
+
+    /// The length of the data stored in the buffer.
+    fn len(&self) -> usize;
+
+    /// Checks if the ring buffer is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the ring buffer is full.
+    fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The remaining space in the buffer.
+    fn remaining(&self) -> usize {
+        self.capacity() - self.len()
+    }
+}
+
+/// Producer side of a ring buffer.
+///
+/// Implemented by [`Producer`](struct.Producer.html) and may be implemented by wrappers around it
+/// or by alternative storage backends, so that generic code such as [`move_items`](fn.move_items.html)
+/// can work with any of them.
+pub trait RbProducer<T>: RbBase<T> {
+    /// Allows to write into ring buffer memory directly.
+    ///
+    /// See [`Producer::push_access`](struct.Producer.html#method.push_access) for details.
+    ///
+    /// # Safety
+    ///
+    /// `f` receives possibly uninitialized memory and must return
+    /// the exact number of elements it has initialized at the beginning of the slices.
+    unsafe fn push_access<F>(&mut self, f: F) -> usize
+    where
+        F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize;
+
+    /// Appends an element to the ring buffer.
+    /// On failure returns an error containing the element that hasn't beed appended.
+    fn push(&mut self, elem: T) -> Result<(), T> {
+        let mut elem_mu = MaybeUninit::new(elem);
+        let n = unsafe {
+            self.push_access(|slice, _| {
+                if !slice.is_empty() {
+                    mem::swap(slice.get_unchecked_mut(0), &mut elem_mu);
+                    1
+                } else {
+                    0
+                }
+            })
+        };
+        match n {
+            0 => Err(unsafe { elem_mu.assume_init() }),
+            1 => Ok(()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Consumer side of a ring buffer.
+///
+/// Implemented by [`Consumer`](struct.Consumer.html) and may be implemented by wrappers around it
+/// or by alternative storage backends, so that generic code such as [`move_items`](fn.move_items.html)
+/// can work with any of them.
+pub trait RbConsumer<T>: RbBase<T> {
+    /// Allows to read from ring buffer memory directly.
+    ///
+    /// See [`Consumer::pop_access`](struct.Consumer.html#method.pop_access) for details.
+    ///
+    /// # Safety
+    ///
+    /// `f` must return the exact number of elements it has moved out from the beginning of the slices.
+    /// These elements are treated as uninitialized after the call.
+    unsafe fn pop_access<F>(&mut self, f: F) -> usize
+    where
+        F: FnOnce(&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) -> usize;
+
+    /// Returns a pair of slices which contain, in order, the contents of the ring buffer.
+    fn as_slices(&self) -> (&[T], &[T]);
+
+    /// Returns a pair of mutable slices which contain, in order, the contents of the ring buffer.
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]);
+
+    /// Removes latest element from the ring buffer and returns it.
+    /// Returns `None` if the ring buffer is empty.
+    fn pop(&mut self) -> Option<T> {
+        let mut elem_mu = MaybeUninit::uninit();
+        let n = unsafe {
+            self.pop_access(|slice, _| {
+                if !slice.is_empty() {
+                    mem::swap(slice.get_unchecked_mut(0), &mut elem_mu);
+                    1
+                } else {
+                    0
+                }
+            })
+        };
+        match n {
+            0 => None,
+            1 => Some(unsafe { elem_mu.assume_init() }),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns an iterator over the elements contained by the ring buffer without removing them.
+    fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (left, right) = self.as_slices();
+        left.iter().chain(right.iter())
+    }
+
+    /// Returns a mutable iterator over the elements contained by the ring buffer without removing them.
+    fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        let (left, right) = self.as_mut_slices();
+        left.iter_mut().chain(right.iter_mut())
+    }
+}
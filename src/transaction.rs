@@ -0,0 +1,159 @@
+use std::{
+    cmp::min,
+    io::{self, Read},
+};
+
+use crate::consumer::Consumer;
+
+/// Consumer reading elements ahead without removing them from the ring buffer.
+///
+/// Created by [`Consumer::begin`](struct.Consumer.html#method.begin).
+/// The elements that have been read are removed on [`commit`](#method.commit),
+/// on [`rollback`](#method.rollback) or drop they are left in place to be read again.
+///
+/// ```rust
+/// # extern crate ringbuf;
+/// # use ringbuf::RingBuffer;
+/// # fn main() {
+/// let (mut prod, mut cons) = RingBuffer::<u8>::new(8).split();
+/// prod.push_slice(&[3, 1, 2]);
+///
+/// let mut tx = cons.begin();
+/// let len = tx.pop().unwrap() as usize;
+/// let mut body = [0; 4];
+/// assert_eq!(tx.pop_slice(&mut body[..len]), 2);
+/// // The message is incomplete, wait for the rest of it.
+/// tx.rollback();
+/// assert_eq!(cons.len(), 3);
+///
+/// prod.push(3).unwrap();
+/// let mut tx = cons.begin();
+/// let len = tx.pop().unwrap() as usize;
+/// assert_eq!(tx.pop_slice(&mut body[..len]), 3);
+/// tx.commit();
+/// assert!(cons.is_empty());
+/// # }
+/// ```
+pub struct Transaction<'a, T> {
+    cons: &'a mut Consumer<T>,
+    /// Number of elements read by the transaction.
+    offset: usize,
+}
+
+impl<T: Sized> Consumer<T> {
+    /// Starts reading elements without removing them.
+    ///
+    /// With `OverwriteOldest` policy the producer doesn't overwrite elements while the transaction exists.
+    pub fn begin(&mut self) -> Transaction<'_, T> {
+        if let Some(lock) = self.lock_mut() {
+            lock.pin();
+        }
+        Transaction {
+            cons: self,
+            offset: 0,
+        }
+    }
+}
+
+impl<'a, T: Sized> Transaction<'a, T> {
+    /// Number of elements read by the transaction so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The number of elements that haven't been read by the transaction.
+    ///
+    /// Actual number may be equal to or greater than the returned value.
+    pub fn len(&self) -> usize {
+        self.cons.len() - self.offset
+    }
+
+    /// Checks if all the elements of the ring buffer have been read by the transaction.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Elements that haven't been read yet, contained in a single storage of the ring buffer.
+    fn rest(&self) -> (&[T], &[T]) {
+        let mut offset = self.offset;
+        for rb in self.cons.rb.chain() {
+            let (left, right) = unsafe { rb.occupied_slices() };
+            if offset < left.len() {
+                return (&left[offset..], right);
+            }
+            offset -= left.len();
+            if offset < right.len() {
+                return (&right[offset..], &[]);
+            }
+            offset -= right.len();
+        }
+        (&[], &[])
+    }
+
+    /// Removes the elements read by the transaction from the ring buffer.
+    ///
+    /// Returns the number of elements been removed.
+    pub fn commit(self) -> usize {
+        let mut count = 0;
+        while count < self.offset {
+            match self.cons.discard(self.offset - count) {
+                0 => break,
+                n => count += n,
+            }
+        }
+        count
+    }
+
+    /// Leaves the elements read by the transaction in the ring buffer.
+    ///
+    /// Same as dropping the transaction.
+    pub fn rollback(self) {}
+}
+
+impl<'a, T: Sized + Copy> Transaction<'a, T> {
+    /// Reads the next element.
+    /// Returns `None` if all the elements have been read.
+    pub fn pop(&mut self) -> Option<T> {
+        let elem = self.rest().0.first().cloned();
+        if elem.is_some() {
+            self.offset += 1;
+        }
+        elem
+    }
+
+    /// Reads elements into the slice.
+    ///
+    /// Returns the number of elements been read.
+    pub fn pop_slice(&mut self, elems: &mut [T]) -> usize {
+        let mut count = 0;
+        while count < elems.len() {
+            let (left, right) = self.rest();
+            let mut n = 0;
+            for src in [left, right].iter() {
+                let m = min(src.len(), elems.len() - count - n);
+                elems[(count + n)..(count + n + m)].copy_from_slice(&src[..m]);
+                n += m;
+            }
+            if n == 0 {
+                break;
+            }
+            count += n;
+            self.offset += n;
+        }
+        count
+    }
+}
+
+impl<'a> Read for Transaction<'a, u8> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let n = self.pop_slice(buffer);
+        if n == 0 && !buffer.is_empty() {
+            Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "No more elements to read",
+            ))
+        } else {
+            Ok(n)
+        }
+    }
+}
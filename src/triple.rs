@@ -0,0 +1,118 @@
+use std::cell::UnsafeCell;
+
+use crate::sync::{Arc, AtomicUsize, Ordering};
+
+/// Bit of the back buffer index set when it holds a value not seen by the consumer.
+const FRESH: usize = 0b100;
+const INDEX: usize = 0b011;
+
+/// Triple buffer passing the most recent value from one thread to another.
+///
+/// Unlike the ring buffer it doesn't queue values: a new value replaces the previous one
+/// even if the consumer hasn't seen it. One buffer is owned by the producer, one by the consumer,
+/// and the third one is exchanged between them, so neither of the parts ever waits for another.
+///
+/// ```rust
+/// # extern crate ringbuf;
+/// # use ringbuf::TripleBuffer;
+/// # fn main() {
+/// let (mut prod, mut cons) = TripleBuffer::new(0).split();
+///
+/// prod.write(1);
+/// prod.write(2);
+/// assert!(cons.updated());
+/// assert_eq!(*cons.read(), 2);
+/// assert!(!cons.updated());
+/// assert_eq!(*cons.read(), 2);
+/// # }
+/// ```
+pub struct TripleBuffer<T> {
+    buffers: [UnsafeCell<T>; 3],
+    /// Index of the buffer being exchanged along with `FRESH` flag.
+    back: AtomicUsize,
+    /// Models of the buffers, checking that accesses to them are synchronized.
+    #[cfg(loom)]
+    slots: [loom::cell::UnsafeCell<()>; 3],
+}
+
+unsafe impl<T: Send> Sync for TripleBuffer<T> {}
+
+impl<T: Clone> TripleBuffer<T> {
+    /// Creates a triple buffer holding `init` as the current value.
+    pub fn new(init: T) -> Self {
+        Self {
+            buffers: [
+                UnsafeCell::new(init.clone()),
+                UnsafeCell::new(init.clone()),
+                UnsafeCell::new(init),
+            ],
+            back: AtomicUsize::new(1),
+            #[cfg(loom)]
+            slots: Default::default(),
+        }
+    }
+}
+
+impl<T> TripleBuffer<T> {
+    /// Splits triple buffer into producer and consumer.
+    pub fn split(self) -> (TripleProducer<T>, TripleConsumer<T>) {
+        let arc = Arc::new(self);
+        (
+            TripleProducer {
+                tb: arc.clone(),
+                index: 0,
+            },
+            TripleConsumer { tb: arc, index: 2 },
+        )
+    }
+
+    /// Marks buffer at `index` as being modified.
+    #[cfg(loom)]
+    fn touch(&self, index: usize) {
+        self.slots[index].with_mut(|_| ());
+    }
+    #[cfg(not(loom))]
+    #[inline]
+    fn touch(&self, _index: usize) {}
+}
+
+/// Producer part of triple buffer.
+pub struct TripleProducer<T> {
+    tb: Arc<TripleBuffer<T>>,
+    /// Index of the buffer owned by the producer.
+    index: usize,
+}
+
+impl<T> TripleProducer<T> {
+    /// Publishes `value` replacing the previous one.
+    pub fn write(&mut self, value: T) {
+        self.tb.touch(self.index);
+        unsafe { *self.tb.buffers[self.index].get() = value };
+        let back = self.tb.back.swap(self.index | FRESH, Ordering::AcqRel);
+        self.index = back & INDEX;
+    }
+}
+
+/// Consumer part of triple buffer.
+pub struct TripleConsumer<T> {
+    tb: Arc<TripleBuffer<T>>,
+    /// Index of the buffer owned by the consumer.
+    index: usize,
+}
+
+impl<T> TripleConsumer<T> {
+    /// Checks if there is a value published since the last `read`.
+    pub fn updated(&self) -> bool {
+        self.tb.back.load(Ordering::Relaxed) & FRESH != 0
+    }
+
+    /// Returns the most recent value published by the producer.
+    pub fn read(&mut self) -> &T {
+        if self.updated() {
+            let back = self.tb.back.swap(self.index, Ordering::AcqRel);
+            self.index = back & INDEX;
+        }
+        self.tb.touch(self.index);
+        unsafe { &*self.tb.buffers[self.index].get() }
+    }
+}
@@ -0,0 +1,33 @@
+use std::thread::{self, Thread};
+
+use crate::sync::{fence, AtomicBool, Mutex, Ordering};
+
+/// Slot for a thread waiting for data or space in the ring buffer.
+#[derive(Default)]
+pub(crate) struct Waker {
+    /// Whether there is a waiting thread, checked by the other side before locking `thread`.
+    armed: AtomicBool,
+    thread: Mutex<Option<Thread>>,
+}
+
+impl Waker {
+    pub(crate) fn register(&self) {
+        *self.thread.lock().unwrap() = Some(thread::current());
+        self.armed.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn unregister(&self) {
+        self.armed.store(false, Ordering::SeqCst);
+        *self.thread.lock().unwrap() = None;
+    }
+
+    /// Called by the other side after it has advanced its position.
+    pub(crate) fn wake(&self) {
+        fence(Ordering::SeqCst);
+        if self.armed.load(Ordering::Relaxed) {
+            if let Some(thread) = &*self.thread.lock().unwrap() {
+                thread.unpark();
+            }
+        }
+    }
+}
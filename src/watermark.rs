@@ -0,0 +1,149 @@
+use crate::{
+    consumer::Consumer,
+    producer::Producer,
+    ring_buffer::RingBuffer,
+    sync::{Arc, AtomicBool, AtomicUsize, Ordering},
+};
+
+/// Occupancy threshold crossed by the ring buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Watermark {
+    /// Length has risen above the high watermark.
+    High,
+    /// Length has fallen below the low watermark after the high one had been crossed.
+    Low,
+}
+
+type Callback = dyn Fn(Watermark) + Send + Sync;
+
+/// Thresholds shared by all storages of the ring buffer.
+pub(crate) struct Watermarks {
+    low: usize,
+    high: usize,
+    /// Number of stored elements in all storages.
+    ///
+    /// Incremented before the elements become available to the consumer and decremented after their removal,
+    /// so it may exceed the actual length for a moment but never falls below it.
+    len: AtomicUsize,
+    /// Whether the high watermark has been crossed and the low one hasn't been crossed since.
+    above: AtomicBool,
+    callback: Option<Box<Callback>>,
+}
+
+impl Watermarks {
+    fn cross(&self, mark: Watermark) {
+        if let Some(callback) = &self.callback {
+            callback(mark);
+        }
+    }
+
+    /// Called by the producer before it makes `n` appended elements available to the consumer.
+    pub(crate) fn on_push(&self, n: usize) {
+        let len = self.len.fetch_add(n, Ordering::AcqRel) + n;
+        if len > self.high && !self.above.swap(true, Ordering::AcqRel) {
+            self.cross(Watermark::High);
+            // The consumer may have drained the buffer before the flag has been set.
+            self.check_low(self.len.load(Ordering::Acquire));
+        }
+    }
+
+    /// Called by the consumer after it has removed `n` elements.
+    pub(crate) fn on_pop(&self, n: usize) {
+        let len = self.len.fetch_sub(n, Ordering::AcqRel) - n;
+        self.check_low(len);
+    }
+
+    fn check_low(&self, len: usize) {
+        if len < self.low && self.above.swap(false, Ordering::AcqRel) {
+            self.cross(Watermark::Low);
+        }
+    }
+
+    fn is_above(&self) -> bool {
+        self.above.load(Ordering::Acquire)
+    }
+}
+
+impl<T: Sized> RingBuffer<T> {
+    /// Sets `low` and `high` watermarks of the ring buffer length.
+    ///
+    /// The ring buffer crosses the high watermark when its length rises above `high`
+    /// and then crosses the low one when its length falls below `low`.
+    /// The state can be checked via `above_watermark` method of the producer or the consumer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` is greater than `high`.
+    pub fn with_watermarks(self, low: usize, high: usize) -> Self {
+        self.set_watermarks(low, high, None)
+    }
+
+    /// Sets `low` and `high` watermarks of the ring buffer length and calls `callback` each time one is crossed.
+    ///
+    /// The callback is called by the side that has made the ring buffer cross the watermark,
+    /// and calls alternate between `Watermark::High` and `Watermark::Low`.
+    ///
+    /// ```rust
+    /// # extern crate ringbuf;
+    /// # use ringbuf::{RingBuffer, Watermark};
+    /// # use std::sync::mpsc;
+    /// # fn main() {
+    /// let (tx, rx) = mpsc::channel();
+    /// let tx = std::sync::Mutex::new(tx);
+    /// let rb = RingBuffer::<i32>::new(4)
+    ///     .with_watermark_callback(1, 2, move |mark| tx.lock().unwrap().send(mark).unwrap());
+    /// let (mut prod, mut cons) = rb.split();
+    ///
+    /// prod.push_slice(&[0, 1, 2]);
+    /// assert_eq!(rx.try_recv(), Ok(Watermark::High));
+    /// cons.pop_slice(&mut [0; 3]);
+    /// assert_eq!(rx.try_recv(), Ok(Watermark::Low));
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` is greater than `high`.
+    pub fn with_watermark_callback<F>(self, low: usize, high: usize, callback: F) -> Self
+    where
+        F: Fn(Watermark) + Send + Sync + 'static,
+    {
+        self.set_watermarks(low, high, Some(Box::new(callback)))
+    }
+
+    fn set_watermarks(mut self, low: usize, high: usize, callback: Option<Box<Callback>>) -> Self {
+        assert!(
+            low <= high,
+            "low watermark {} exceeds high watermark {}",
+            low,
+            high
+        );
+        let len = self.len();
+        self.watermarks = Some(Arc::new(Watermarks {
+            low,
+            high,
+            len: AtomicUsize::new(len),
+            above: AtomicBool::new(len > high),
+            callback,
+        }));
+        self
+    }
+}
+
+impl<T: Sized> Producer<T> {
+    /// Checks if the ring buffer has crossed the high watermark and hasn't crossed the low one since.
+    ///
+    /// Returns `false` if no watermarks are set.
+    pub fn above_watermark(&self) -> bool {
+        self.rb.watermarks.as_ref().is_some_and(|w| w.is_above())
+    }
+}
+
+impl<T: Sized> Consumer<T> {
+    /// Checks if the ring buffer has crossed the high watermark and hasn't crossed the low one since.
+    ///
+    /// Returns `false` if no watermarks are set.
+    pub fn above_watermark(&self) -> bool {
+        self.rb.watermarks.as_ref().is_some_and(|w| w.is_above())
+    }
+}